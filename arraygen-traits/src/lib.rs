@@ -0,0 +1,99 @@
+//!
+//! This crate provides the traits implemented by the methods that the [`Arraygen`](https://docs.rs/arraygen) derive
+//! macro generates, so generic code can consume the arrays of any struct deriving `Arraygen`.
+//!
+//! The traits are implemented for the `gen_array` methods with a `tag` clause. The tag is a type chosen by you, which
+//! identifies the method in the trait bounds, since a struct may implement the same trait for several methods:
+//!
+//! ```rust
+//! use arraygen::Arraygen;
+//! use arraygen_traits::FieldArray;
+//!
+//! struct Prices;
+//!
+//! #[derive(Arraygen)]
+//! #[gen_array(fn get_prices: &f32, implicit_select_all: f32, tag = Prices)]
+//! struct Groceries {
+//!     water: f32,
+//!     oil: f32,
+//! }
+//!
+//! #[derive(Arraygen)]
+//! #[gen_array(fn get_prices: &f32, implicit_select_all: f32, tag = Prices)]
+//! struct Tools {
+//!     hammer: f32,
+//! }
+//!
+//! fn total<T>(items: &T) -> f32
+//! where
+//!     T: for<'a> FieldArray<Prices, Item<'a> = &'a f32>,
+//! {
+//!     items.field_array().into_iter().sum()
+//! }
+//!
+//! assert_eq!(total(&Groceries { water: 1.0, oil: 3.0 }), 4.0);
+//! assert_eq!(total(&Tools { hammer: 10.0 }), 10.0);
+//! ```
+//!
+//! The trait implemented by each method depends on its receiver:
+//!
+//! * [`FieldArray`] for methods taking `&self`.
+//! * [`FieldArrayMut`] for methods taking `&mut self`, which are the ones returning mutable references.
+//! * [`IntoFieldArray`] for methods taking `self`, which are the ones with the `by_value` clause.
+//!
+//! Structs also implement [`FieldArrayLen`] for each tagged method, since the length of their arrays is known at compile time.
+//!
+//! The `Arraygen` derive macro is re-exported by this crate when the `derive` feature is enabled.
+
+#[cfg(feature = "derive")]
+pub use arraygen::Arraygen;
+
+/// Implemented by the `gen_array` methods taking `&self` that contain the `tag` clause.
+pub trait FieldArray<Tag> {
+    /// The return type of the `gen_array` method, or the pair of the name and that type when it has the `with_names` clause.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// The type returned by the `gen_array` method: an array for structs, and a `Vec` for enums.
+    type Array<'a>: IntoIterator<Item = Self::Item<'a>>
+    where
+        Self: 'a;
+
+    /// Calls the tagged `gen_array` method.
+    fn field_array(&self) -> Self::Array<'_>;
+}
+
+/// Implemented by the `gen_array` methods taking `&mut self` that contain the `tag` clause.
+pub trait FieldArrayMut<Tag> {
+    /// The return type of the `gen_array` method, or the pair of the name and that type when it has the `with_names` clause.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// The type returned by the `gen_array` method: an array for structs, and a `Vec` for enums.
+    type Array<'a>: IntoIterator<Item = Self::Item<'a>>
+    where
+        Self: 'a;
+
+    /// Calls the tagged `gen_array` method.
+    fn field_array_mut(&mut self) -> Self::Array<'_>;
+}
+
+/// Implemented by the `gen_array` methods taking `self` that contain the `tag` clause.
+pub trait IntoFieldArray<Tag> {
+    /// The return type of the `gen_array` method, or the pair of the name and that type when it has the `with_names` clause.
+    type Item;
+
+    /// The type returned by the `gen_array` method: an array for structs, and a `Vec` for enums.
+    type Array: IntoIterator<Item = Self::Item>;
+
+    /// Calls the tagged `gen_array` method.
+    fn into_field_array(self) -> Self::Array;
+}
+
+/// Implemented by structs for each `gen_array` method that contains the `tag` clause.
+pub trait FieldArrayLen<Tag> {
+    /// The length of the array returned by the tagged `gen_array` method.
+    const LEN: usize;
+}
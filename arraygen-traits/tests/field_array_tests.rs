@@ -0,0 +1,139 @@
+extern crate arraygen;
+extern crate arraygen_traits;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+    use arraygen_traits::{FieldArray, FieldArrayLen, FieldArrayMut, IntoFieldArray};
+    use std::fmt::Display;
+
+    struct Prices;
+    struct Labels;
+
+    #[derive(Arraygen)]
+    #[gen_array(fn prices: &f32, implicit_select_all: f32, tag = Prices)]
+    #[gen_array(fn labels: &dyn Display, implicit_select_all: f32, &str, tag = Labels)]
+    struct Groceries<'a> {
+        water: f32,
+        oil: f32,
+        name: &'a str,
+    }
+
+    #[derive(Arraygen)]
+    #[gen_array(fn prices: &f32, implicit_select_all: f32, tag = Prices)]
+    struct Tools {
+        hammer: f32,
+    }
+
+    fn total<T>(items: &T) -> f32
+    where
+        T: for<'a> FieldArray<Prices, Item<'a> = &'a f32>,
+    {
+        items.field_array().into_iter().sum()
+    }
+
+    fn describe<T: FieldArray<Labels>>(items: &T) -> String
+    where
+        for<'a> T::Item<'a>: Display,
+    {
+        items
+            .field_array()
+            .into_iter()
+            .map(|label| label.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[test]
+    fn test_field_array___with_different_structs___can_be_used_generically() {
+        let groceries = Groceries {
+            water: 1.0,
+            oil: 3.0,
+            name: "groceries",
+        };
+
+        assert_eq!(total(&groceries), 4.0);
+        assert_eq!(total(&Tools { hammer: 10.0 }), 10.0);
+        assert_eq!(describe(&groceries), "1, 3, groceries");
+    }
+
+    #[test]
+    fn test_field_array_len___with_tag___returns_length_of_array() {
+        fn len<T: FieldArrayLen<Prices>>() -> usize {
+            T::LEN
+        }
+
+        assert_eq!(len::<Groceries>(), 2);
+        assert_eq!(len::<Tools>(), 1);
+    }
+
+    #[test]
+    fn test_field_array_mut___with_mutable_references___modifies_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices_mut: &mut f32, implicit_select_all: f32, tag = Prices)]
+        struct Sut {
+            a: f32,
+            b: f32,
+        }
+
+        fn double<T: for<'a> FieldArrayMut<Prices, Item<'a> = &'a mut f32>>(items: &mut T) {
+            for price in items.field_array_mut() {
+                *price *= 2.0;
+            }
+        }
+
+        let mut actual = Sut { a: 1.0, b: 2.0 };
+        double(&mut actual);
+
+        assert_eq!((actual.a, actual.b), (2.0, 4.0));
+    }
+
+    #[test]
+    fn test_into_field_array___with_by_value_and_with_names___moves_named_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn into_names: String, implicit_select_all: String, by_value, with_names, tag = Labels)]
+        struct Sut {
+            first: String,
+            last: String,
+        }
+
+        fn collect<T: IntoFieldArray<Labels, Item = (&'static str, String)>>(
+            items: T,
+        ) -> Vec<String> {
+            items
+                .into_field_array()
+                .into_iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect()
+        }
+
+        let actual = Sut {
+            first: "Ada".into(),
+            last: "Lovelace".into(),
+        };
+
+        assert_eq!(collect(actual), ["first=Ada", "last=Lovelace"]);
+    }
+
+    #[test]
+    fn test_field_array___on_enums_and_generic_structs___returns_selected_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: &f32, implicit_select_all: f32, tag = Prices)]
+        enum Item {
+            Single(f32),
+            Pair(f32, f32),
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: &T, generic_params, tag = Prices)]
+        struct Wrapper<T> {
+            a: T,
+            b: T,
+        }
+
+        assert_eq!(total(&Item::Pair(1.0, 2.0)), 3.0);
+        assert_eq!(total(&Item::Single(5.0)), 5.0);
+        assert_eq!(total(&Wrapper { a: 1.5, b: 2.5 }), 4.0);
+    }
+}
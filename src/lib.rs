@@ -49,7 +49,7 @@
 //! }
 //! ```
 
-#![allow(clippy::eval_order_dependence)]
+#![allow(clippy::mixed_read_write_in_expression)]
 
 extern crate proc_macro;
 
@@ -59,6 +59,18 @@ const DERIVE_NAME: &str = "Arraygen";
 const DECL_FN_NAME: &str = "gen_array";
 const FIELD_SELECTOR_NAME: &str = "in_array";
 const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
+const NAMES_CLAUSE_NAME: &str = "names";
+const WITH_NAMES_CLAUSE_NAME: &str = "with_names";
+const BY_VALUE_CLAUSE_NAME: &str = "by_value";
+const ALLOW_EMPTY_CLAUSE_NAME: &str = "allow_empty";
+const EXCEPT_CLAUSE_NAME: &str = "except";
+const SELECT_BY_NAME_CLAUSE_NAME: &str = "select_by_name";
+const SELECT_VIS_CLAUSE_NAME: &str = "select_vis";
+const GENERIC_PARAMS_CLAUSE_NAME: &str = "generic_params";
+const TAG_CLAUSE_NAME: &str = "tag";
+const IMPL_TRAIT_CLAUSE_NAME: &str = "impl_trait";
+const GET_CLAUSE_NAME: &str = "get";
+const GET_BY_NAME_CLAUSE_NAME: &str = "get_by_name";
 
 /// The `Arraygen` derive allows you to use the attribute `gen_array` at the struct level, and the attribute `in_array` in each contained field.
 ///
@@ -77,8 +89,14 @@ const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
 ///
 /// There is no limit to the number of methods you can declare.
 ///
+/// The generated methods are emitted in the same order as their `gen_array` declarations, so the expansion of the derive is
+/// always the same for the same input. Declaring two methods with the same name is an error.
+///
 /// By default, these new `Arraygen` methods return arrays of length 0. That's not very useful, but that's why we also have the next attribute: `in_array`.
 ///
+/// The compiler will warn you about methods returning arrays of length 0. If that's intended, you may silence that warning by
+/// adding the `allow_empty` clause to the declaration, like this: `#[gen_array(fn your_method_name: YourReturnType, allow_empty)]`.
+///
 ///
 /// # in_array
 ///
@@ -167,7 +185,12 @@ const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
 ///
 /// * **cast** : This decorator casts the current field to the return type of the `gen_array` method where it will be included.
 /// * **unsafe_transmute** : This one uses [`unsafe { std::mem::transmute }`](https://doc.rust-lang.org/std/mem/fn.transmute.html) to force an unsafe cast of the current field to the return type of the `gen_array` method.
+/// * **into** : Converts a clone of the current field to the return type of the `gen_array` method using [`Into`](https://doc.rust-lang.org/std/convert/trait.Into.html). When the method has the `by_value` clause, the field is moved instead of cloned.
+/// * **try_into** : Like `into`, but using [`TryInto`](https://doc.rust-lang.org/std/convert/trait.TryInto.html). The `gen_array` method then returns a `Result` with the array, or with the first conversion error boxed as a `Box<dyn std::error::Error + Send + Sync>`.
+/// * **with = path::to_function** : Calls the given function with the current field, and includes its result in the array. The field is passed as `&field`, as `&mut field` when the return type of the `gen_array` method is a mutable reference, or moved when the method has the `by_value` clause.
 /// * **override_implicit** : In case the current field is already selected by an `implicit_select_all` clause for this `gen_array` (more about this clause later), you may use `override_implicit` to apply different decorators to the current field.
+/// * **skip** : Excludes the current field from the `implicit_select_all` clause of this `gen_array`. See [Excluding Fields from Implicit Selection](#excluding-fields-from-implicit-selection).
+/// * **flatten** : Splices the elements returned by the method with the same name in the type of the current field, which must also derive `Arraygen`. It can't be combined with casting decorators.
 ///
 /// Casting example:
 ///
@@ -196,6 +219,311 @@ const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
 ///
 /// ```
 ///
+/// Conversion example:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn floats: f64)]
+/// #[gen_array(fn bytes: u8)]
+/// struct Numbers {
+///     #[in_array(floats { into }, bytes { try_into })]
+///     one: u32,
+///
+///     #[in_array(floats { into }, bytes { try_into })]
+///     two: i16,
+/// }
+///
+/// let numbers = Numbers { one: 1, two: 300 };
+///
+/// assert_eq!(numbers.floats(), [1.0, 300.0]);
+/// assert!(numbers.bytes().is_err());
+/// ```
+///
+/// The `into` and `try_into` decorators produce owned values, so they can't be used when the return type is a reference.
+///
+/// Example with a custom function:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// fn to_label(value: &i32) -> String {
+///     format!("#{}", value)
+/// }
+///
+/// #[derive(Arraygen)]
+/// #[gen_array(fn labels: String, implicit_select_all { with = to_label }: i32)]
+/// struct Numbers {
+///     one: i32,
+///     two: i32,
+/// }
+///
+/// let numbers = Numbers { one: 1, two: 2 };
+///
+/// assert_eq!(numbers.labels(), ["#1", "#2"]);
+/// ```
+///
+/// Flattening example:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn prices: f32, implicit_select_all: f32)]
+/// struct Fruits {
+///     apple: f32,
+///     pear: f32,
+/// }
+///
+/// #[derive(Arraygen)]
+/// #[gen_array(fn prices: f32, implicit_select_all: f32)]
+/// struct Groceries {
+///     milk: f32,
+///     #[in_array(prices { flatten })]
+///     fruits: Fruits,
+///     bread: f32,
+/// }
+///
+/// let groceries = Groceries { milk: 1.0, fruits: Fruits { apple: 2.0, pear: 3.0 }, bread: 4.0 };
+///
+/// assert_eq!(groceries.prices(), [1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(Groceries::PRICES_LEN, 4);
+/// ```
+///
+/// The length of the array is computed from the associated constant of the flattened type, like `Fruits::PRICES_LEN`. Since
+/// array lengths can't depend on generic parameters, the type of a flattened field in a struct can't mention the type or const
+/// parameters of that struct, and doing so is reported as an error. Enums have no such restriction, because they return a `Vec`.
+/// Methods containing flattened fields can't have the `names` clause, and with `with_names` the names of the flattened elements
+/// are the ones given by the flattened type.
+///
+///
+/// # Tuple Structs
+///
+/// Tuple structs are supported too. In that case, `in_array` is placed right before each positional field,
+/// and the generated method accesses the fields by their index.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn red_and_blue: &u8)]
+/// struct Rgb(#[in_array(red_and_blue)] u8, u8, #[in_array(red_and_blue)] u8);
+///
+/// let color = Rgb(10, 20, 30);
+///
+/// assert_eq!(color.red_and_blue(), [&10, &30]);
+/// ```
+///
+/// The generated method in this case is equivalent to `[&self.0, &self.2]`.
+///
+///
+/// # Enums
+///
+/// `Arraygen` may also be derived on enums. In that case, `in_array` is placed on the fields of each variant,
+/// and the generated method returns the selected fields of whichever variant is active.
+///
+/// Since each variant may contain a different number of selected fields, methods generated for enums
+/// return a `Vec` instead of an array.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn numbers: &i32)]
+/// enum Shape {
+///     Point(#[in_array(numbers)] i32, #[in_array(numbers)] i32),
+///     Circle {
+///         #[in_array(numbers)]
+///         radius: i32,
+///         name: String,
+///     },
+///     Nothing,
+/// }
+///
+/// assert_eq!(Shape::Point(1, 2).numbers(), [&1, &2]);
+/// assert_eq!(Shape::Circle { radius: 3, name: "c".into() }.numbers(), [&3]);
+/// assert!(Shape::Nothing.numbers().is_empty());
+/// ```
+///
+/// The `implicit_select_all` clause works on enums as well, selecting the matching fields of every variant.
+///
+///
+/// # Moving Fields Out
+///
+/// Generated methods take `&self`, or `&mut self` when returning mutable references. If you add the `by_value` clause
+/// to your `gen_array` declaration, the generated method takes `self` instead, moving the selected fields out of the struct.
+/// This is useful for non-`Copy` types, when you don't need the struct anymore.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn into_names: String, by_value)]
+/// struct Person {
+///     #[in_array(into_names)]
+///     first_name: String,
+///     #[in_array(into_names)]
+///     last_name: String,
+/// }
+///
+/// let person = Person { first_name: "Ada".into(), last_name: "Lovelace".into() };
+///
+/// assert_eq!(person.into_names(), ["Ada", "Lovelace"]);
+/// ```
+///
+/// The `by_value` clause can't be used when the return type is a reference.
+///
+///
+/// # Array Lengths
+///
+/// Alongside each method generated for a struct, `Arraygen` also generates an associated constant with the length of
+/// the returned array. Its name is the method name in uppercase followed by `_LEN`, and it has the same visibility as the method.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_all_prices: f32, implicit_select_all: f32)]
+/// struct Prices {
+///     water: f32,
+///     oil: f32,
+/// }
+///
+/// struct History {
+///     last_prices: [f32; Prices::GET_ALL_PRICES_LEN],
+/// }
+///
+/// let prices = Prices { water: 1.0, oil: 3.0 };
+/// let history = History { last_prices: prices.get_all_prices() };
+///
+/// assert_eq!(Prices::GET_ALL_PRICES_LEN, 2);
+/// ```
+///
+/// This constant is not generated for enums, since their methods may return a different number of elements for each variant.
+///
+///
+/// # Field Names
+///
+/// You may add the `names` clause to your `gen_array` declaration to also generate an associated `const fn`
+/// returning the names of the selected fields, in the same order as the values returned by the method.
+///
+/// ```ignore
+/// #[gen_array(?visibility fn your_method_name: YourReturnType, names = your_names_fn_name)]
+/// ```
+///
+/// Example:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn prices: f32, names = price_names)]
+/// struct Prices {
+///     #[in_array(prices)]
+///     water: f32,
+///     #[in_array(prices)]
+///     oil: f32,
+/// }
+///
+/// let prices = Prices { water: 1.0, oil: 3.0 };
+///
+/// assert_eq!(Prices::price_names(), ["water", "oil"]);
+/// assert_eq!(prices.prices(), [1.0, 3.0]);
+/// ```
+///
+/// Fields of tuple structs are named by their index (`"0"`, `"1"`, ...). The `names` clause is not supported on enums.
+///
+/// If you rather want to get the names and the values with a single call, add the `with_names` clause instead.
+/// The generated method will then return an array of `(&'static str, YourReturnType)` pairs:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn priced: &f32, with_names, implicit_select_all: f32)]
+/// struct Prices {
+///     water: f32,
+///     oil: f32,
+/// }
+///
+/// let prices = Prices { water: 1.0, oil: 3.0 };
+///
+/// assert_eq!(prices.priced(), [("water", &1.0), ("oil", &3.0)]);
+/// ```
+///
+/// # Indexed Access
+///
+/// When you need a single element, you may add the `get` clause to your `gen_array` declaration to also generate a method
+/// returning the element at the given index, without building the whole array:
+///
+/// ```ignore
+/// #[gen_array(?visibility fn your_method_name: YourReturnType, get = your_get_fn_name)]
+/// ```
+///
+/// The generated method takes the same receiver as the `gen_array` method, and returns an `Option` with the element that
+/// the array would contain at that index. Declaring it on a method returning mutable references gives you a mutable accessor:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn prices: &f32, implicit_select_all: f32, get = prices_get)]
+/// #[gen_array(fn prices_mut: &mut f32, implicit_select_all: f32, get = prices_get_mut)]
+/// struct Prices {
+///     water: f32,
+///     oil: f32,
+/// }
+///
+/// let mut prices = Prices { water: 1.0, oil: 3.0 };
+///
+/// if let Some(oil) = prices.prices_get_mut(1) {
+///     *oil = 4.0;
+/// }
+///
+/// assert_eq!(prices.prices_get(1), Some(&4.0));
+/// assert_eq!(prices.prices_get(2), None);
+/// ```
+///
+/// Only the requested element is converted, so when the method contains `try_into` decorators, the result is an error only if
+/// the conversion of that element fails. The elements of flattened fields are taken from the array of their own method.
+///
+/// Similarly, the `get_by_name` clause generates a method returning the element of the field with the given name, using the
+/// same names as the `names` clause:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn prices: &f32, implicit_select_all: f32, get_by_name = prices_by_name)]
+/// #[gen_array(fn prices_mut: &mut f32, implicit_select_all: f32, get_by_name = prices_by_name_mut)]
+/// struct Prices {
+///     water: f32,
+///     oil: f32,
+/// }
+///
+/// let mut prices = Prices { water: 1.0, oil: 3.0 };
+///
+/// if let Some(water) = prices.prices_by_name_mut("water") {
+///     *water = 2.0;
+/// }
+///
+/// assert_eq!(prices.prices_by_name("water"), Some(&2.0));
+/// assert_eq!(prices.prices_by_name("salt"), None);
+/// ```
+///
+/// On enums, only the fields of the active variant are found. Methods with flattened fields can't have the `get_by_name` clause.
+///
+///
+/// # Conditional Compilation
+///
+/// Fields and variants disabled by a `#[cfg(...)]` attribute are removed by the compiler before `Arraygen` sees them,
+/// so they are never included in the generated arrays, not even through `implicit_select_all`. The lengths of the
+/// arrays, the associated `_LEN` constants and the field names follow the active configuration.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_values: &i32, implicit_select_all: i32)]
+/// struct Values {
+///     cpu: i32,
+///     #[cfg(feature = "gpu")]
+///     gpu: i32,
+/// }
+///
+/// let values = Values { cpu: 1 };
+///
+/// assert_eq!(values.get_values(), [&1]);
+/// ```
 ///
 ///
 /// # Trait Objects
@@ -334,6 +662,10 @@ const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
 ///
 /// See which decorators you may use in the previous `in_array` section.
 ///
+/// Types are compared as written, so `implicit_select_all: HashMap<i32, i32>` won't select a field of type `std::collections::HashMap<i32, i32>`.
+/// The only exception are the types and traits from the standard prelude, so `String` and `std::string::String`,
+/// or `Vec<u8>` and `alloc::vec::Vec<u8>`, are considered the same type.
+///
 /// # Implicit selection of Fields with Type Wildcards
 ///
 /// You may use *Type Wildcards* (`_`) on the `implicit_select_all` clause.
@@ -362,7 +694,7 @@ const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
 ///         *self = None;
 ///     }
 /// }
-
+///
 /// trait ResetOption {
 ///     fn reset(&mut self);
 /// }
@@ -378,7 +710,207 @@ const IMPLICIT_SELECT_ALL_NAME: &str = "implicit_select_all";
 /// 
 /// As you may see above, using *Type Wildcards* in conjuction with [Trait Objects](#trait-objects) allows you to accomplish very powerful constructs in a very succinct manner.
 ///
-
+/// Wildcards may also be used for references, lifetimes and trait objects:
+///
+/// * `&_` matches any shared reference, and `&mut _` any mutable reference.
+/// * `'_` matches any lifetime, like in `&'_ str` or `Cow<'_, str>`. References without lifetime in the clause also match any lifetime.
+/// * `dyn _` matches any trait object, like in `Box<dyn _>`.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn borrowed: &dyn std::fmt::Debug, implicit_select_all: &_)]
+/// struct Borrowed<'a> {
+///     name: &'a str,
+///     bytes: &'a [u8],
+///     count: &'a mut i32,
+/// }
+///
+/// let mut count = 1;
+/// let borrowed = Borrowed { name: "foo", bytes: &[1, 2], count: &mut count };
+///
+/// assert_eq!(borrowed.borrowed().len(), 2);
+/// ```
+///
+/// # Implicit selection of Fields by their Names
+///
+/// Fields may also be selected by their names with the `select_by_name` clause, which takes a comma separated list of
+/// patterns. In those patterns, `*` matches any sequence of characters and `?` matches exactly one character.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_timeouts: u64, select_by_name { cast }: "*_timeout_ms")]
+/// struct Config {
+///     read_timeout_ms: u32,
+///     write_timeout_ms: u64,
+///     retries: u8,
+/// }
+///
+/// let config = Config { read_timeout_ms: 100, write_timeout_ms: 200, retries: 3 };
+///
+/// assert_eq!(config.get_timeouts(), [100, 200]);
+/// ```
+///
+/// Like `implicit_select_all`, this clause may include decorators, and it may be combined with the other selection clauses.
+/// Fields matched by several selection clauses are included only once, using the decorators of the first matching clause
+/// in this order: `implicit_select_all`, `select_by_name`, `select_vis` and `generic_params`.
+///
+/// # Implicit selection of Fields by their Visibility
+///
+/// The `select_vis` clause selects the fields declared with any of the given visibilities, like `pub` or `pub(crate)`.
+/// The visibility must match exactly, so `select_vis: pub` doesn't select `pub(crate)` fields.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_public_values: &i32, select_vis: pub)]
+/// struct Values {
+///     pub a: i32,
+///     b: i32,
+///     pub c: i32,
+/// }
+///
+/// let values = Values { a: 1, b: 2, c: 3 };
+///
+/// assert_eq!(values.get_public_values(), [&1, &3]);
+/// ```
+///
+/// Like `implicit_select_all`, this clause may include decorators. It can't be used on enums, since their fields have no visibility.
+///
+/// # Implicit selection of Generic Fields
+///
+/// Type parameters of the struct may be used in the `implicit_select_all` clause like any other type. The generated methods
+/// are implemented with the same generics and bounds as the struct, so fields of a type parameter may be returned as
+/// trait objects of its bounds:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// # use std::fmt::Debug;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_debuggables: &dyn Debug, implicit_select_all: T)]
+/// struct Wrapper<T: Debug> {
+///     a: T,
+///     b: T,
+///     c: u8,
+/// }
+///
+/// let wrapper = Wrapper { a: "foo", b: "bar", c: 1 };
+///
+/// assert_eq!(format!("{:?}", wrapper.get_debuggables()), r#"["foo", "bar"]"#);
+/// ```
+///
+/// The `generic_params` clause selects all the fields whose type is one of the type parameters of the struct, whatever
+/// their name is. Like the other selection clauses, it may include decorators, as in `generic_params { cast }`.
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// # use std::fmt::Display;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_displayables: &dyn Display, generic_params)]
+/// struct Pair<K: Display, V: Display> {
+///     key: K,
+///     value: V,
+///     hits: u32,
+/// }
+///
+/// let pair = Pair { key: 'a', value: 1.5, hits: 3 };
+///
+/// assert_eq!(pair.get_displayables().map(|d| d.to_string()), ["a", "1.5"]);
+/// ```
+///
+/// Only fields declared exactly as a type parameter are selected, so fields like `Option<T>` or `&'a T` are not.
+///
+/// # Excluding Fields from Implicit Selection
+///
+/// Fields selected by `implicit_select_all`, `select_by_name`, `select_vis` or `generic_params` may be excluded by name with the `except` clause, which takes a comma separated list of field names
+/// (or field indexes for tuple structs):
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_all_prices: f32, implicit_select_all: f32, except: discount)]
+/// struct Prices {
+///     water: f32,
+///     oil: f32,
+///     discount: f32,
+/// }
+///
+/// let prices = Prices { water: 1.0, oil: 3.0, discount: 0.5 };
+///
+/// assert_eq!(prices.get_all_prices(), [1.0, 3.0]);
+/// ```
+///
+/// Alternatively, a field may opt out of the implicit selection of a `gen_array` method with the `skip` decorator, like in
+/// `#[in_array(get_all_prices { skip })]`. The `skip` decorator can't be combined with other decorators, and it can only be used
+/// on fields that the method would otherwise select implicitly.
+///
+/// Excluded fields may still be included explicitly with an `in_array` attribute.
+///
+/// # Implementing Traits
+///
+/// The generated methods are inherent methods, so generic code can't call them. With the `tag = Path` clause, the
+/// `gen_array` method also implements the traits of the [`arraygen-traits`](https://docs.rs/arraygen-traits) crate,
+/// using the given type as the tag that identifies the method:
+///
+/// ```ignore
+/// use arraygen_traits::{Arraygen, FieldArray};
+///
+/// struct Prices;
+///
+/// #[derive(Arraygen)]
+/// #[gen_array(fn get_prices: &f32, implicit_select_all: f32, tag = Prices)]
+/// struct Groceries {
+///     water: f32,
+///     oil: f32,
+/// }
+///
+/// fn total<T: for<'a> FieldArray<Prices, Item<'a> = &'a f32>>(items: &T) -> f32 {
+///     items.field_array().into_iter().sum()
+/// }
+/// ```
+///
+/// Methods taking `&self` implement `FieldArray`, methods returning mutable references implement `FieldArrayMut`, and
+/// methods with the `by_value` clause implement `IntoFieldArray`. Structs also implement `FieldArrayLen` with the length of the
+/// array. The `arraygen-traits` crate re-exports this derive macro when its `derive` feature is enabled.
+///
+/// The `tag` clause can't be used in methods returning a `Result`, like the ones with fields using the `try_into` decorator.
+///
+/// A `gen_array` method may also implement a trait of your own with the `impl_trait = Path` clause. The trait must
+/// contain a single method with the same signature as the generated one, which is then emitted in an impl of that trait
+/// instead of in an inherent impl:
+///
+/// ```rust
+/// # use arraygen::Arraygen;
+/// trait Sensor {
+///     fn read(&self) -> i32;
+/// }
+///
+/// trait HasSensors {
+///     fn sensors(&self) -> [&dyn Sensor; 2];
+/// }
+///
+/// struct Thermometer(i32);
+///
+/// impl Sensor for Thermometer {
+///     fn read(&self) -> i32 { self.0 }
+/// }
+///
+/// #[derive(Arraygen)]
+/// #[gen_array(fn sensors: &dyn Sensor, implicit_select_all: Thermometer, impl_trait = HasSensors)]
+/// struct Kitchen {
+///     fridge: Thermometer,
+///     oven: Thermometer,
+/// }
+///
+/// fn sum_readings<T: HasSensors>(item: &T) -> i32 {
+///     item.sensors().iter().map(|sensor| sensor.read()).sum()
+/// }
+///
+/// assert_eq!(sum_readings(&Kitchen { fridge: Thermometer(4), oven: Thermometer(180) }), 184);
+/// ```
+///
+/// Methods with the `impl_trait` clause can't have a visibility, since trait methods are as visible as their trait.
 #[proc_macro_derive(Arraygen, attributes(gen_array, in_array))]
 pub fn arraygen(input: TokenStream) -> TokenStream {
     transform_context::transform_ast(input)
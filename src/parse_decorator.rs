@@ -1,18 +1,52 @@
 use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::token;
-use syn::{braced, Error, Ident, Token};
+use syn::{braced, Error, Expr, Ident, Token};
 
+use crate::utils::make_suggestion;
 use crate::FIELD_SELECTOR_NAME;
 
+const DECORATOR_NAMES: &[&str] = &[
+    "override_implicit",
+    "cast",
+    "unsafe_transmute",
+    "into",
+    "try_into",
+    "with",
+    "skip",
+    "flatten",
+];
+
 #[derive(Clone, PartialEq)]
 pub enum CastKind {
     SafeCast,
     UnsafeTransmute,
+    Into,
+    TryInto,
+    With(Expr),
+}
+
+impl CastKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CastKind::SafeCast => "cast",
+            CastKind::UnsafeTransmute => "unsafe_transmute",
+            CastKind::Into => "into",
+            CastKind::TryInto => "try_into",
+            CastKind::With(_) => "with",
+        }
+    }
+
+    // Conversion traits produce owned values, so they can't be used to build arrays of references.
+    pub fn returns_owned_value(&self) -> bool {
+        matches!(self, CastKind::Into | CastKind::TryInto)
+    }
 }
 
 pub struct Decorator {
     pub override_implicit: bool,
+    pub skip: bool,
+    pub flatten: bool,
     pub cast: Option<CastKind>,
 }
 
@@ -20,6 +54,8 @@ impl Decorator {
     pub fn new() -> Decorator {
         Decorator {
             override_implicit: false,
+            skip: false,
+            flatten: false,
             cast: None,
         }
     }
@@ -31,27 +67,90 @@ impl Parse for Decorator {
         if input.peek(token::Brace) {
             let content;
             let _ = braced!(content in input);
-            for ident in Punctuated::<Ident, Token![,]>::parse_separated_nonempty(&content)?.iter() {
-                match ident.to_string().as_ref() {
-                    "override_implicit" if !decorator.override_implicit => {
+            let entries =
+                Punctuated::<_, Token![,]>::parse_separated_nonempty_with(&content, parse_entry)?;
+            for (ident, value) in entries.iter().cloned() {
+                match (ident.to_string().as_ref(), value) {
+                    ("override_implicit", None) if !decorator.override_implicit => {
                         decorator.override_implicit = true
                     }
-                    "cast" if decorator.cast.is_none() => decorator.cast = Some(CastKind::SafeCast),
-                    "unsafe_transmute" if decorator.cast.is_none() => {
+                    ("skip", None) if !decorator.skip => decorator.skip = true,
+                    ("flatten", None) if !decorator.flatten => decorator.flatten = true,
+                    ("cast", None) if decorator.cast.is_none() => {
+                        decorator.cast = Some(CastKind::SafeCast)
+                    }
+                    ("unsafe_transmute", None) if decorator.cast.is_none() => {
                         decorator.cast = Some(CastKind::UnsafeTransmute)
                     }
-                    decorator => {
+                    ("into", None) if decorator.cast.is_none() => {
+                        decorator.cast = Some(CastKind::Into)
+                    }
+                    ("try_into", None) if decorator.cast.is_none() => {
+                        decorator.cast = Some(CastKind::TryInto)
+                    }
+                    ("with", Some(function)) if decorator.cast.is_none() => {
+                        decorator.cast = Some(CastKind::With(function))
+                    }
+                    ("with", None) => {
                         return Err(Error::new_spanned(
-                            ident,
+                            &ident,
+                            "decorator 'with' requires a function, like 'with = path::to_function'",
+                        ))
+                    }
+                    (name, Some(value)) if DECORATOR_NAMES.contains(&name) => {
+                        return Err(Error::new_spanned(
+                            value,
+                            format!("decorator '{}' doesn't accept a value", name),
+                        ))
+                    }
+                    (name, _) => {
+                        return Err(Error::new_spanned(
+                            &ident,
                             format!(
-                                "{} doesn't allow '{}' as decorator here",
-                                FIELD_SELECTOR_NAME, decorator
+                                "{} doesn't allow '{}' as decorator here{}",
+                                FIELD_SELECTOR_NAME,
+                                name,
+                                make_suggestion(
+                                    name,
+                                    DECORATOR_NAMES.iter().copied(),
+                                    "decorators"
+                                )
                             ),
                         ))
                     }
                 };
             }
+            if decorator.skip && entries.len() > 1 {
+                let (skip, _) = entries.iter().find(|(ident, _)| ident == "skip").unwrap();
+                return Err(Error::new_spanned(
+                    skip,
+                    "decorator 'skip' can't be combined with other decorators",
+                ));
+            }
+            if let (true, Some(cast)) = (decorator.flatten, decorator.cast.as_ref()) {
+                let (flatten, _) = entries
+                    .iter()
+                    .find(|(ident, _)| ident == "flatten")
+                    .unwrap();
+                return Err(Error::new_spanned(
+                    flatten,
+                    format!(
+                        "decorator 'flatten' can't be combined with '{}'",
+                        cast.name()
+                    ),
+                ));
+            }
         }
         Ok(decorator)
     }
 }
+
+fn parse_entry(input: ParseStream) -> Result<(Ident, Option<Expr>)> {
+    let ident: Ident = input.parse()?;
+    if input.peek(Token![=]) {
+        let _: Token![=] = input.parse()?;
+        Ok((ident, Some(input.parse()?)))
+    } else {
+        Ok((ident, None))
+    }
+}
@@ -1,53 +1,120 @@
-use crate::DECL_FN_NAME;
-use std::collections::HashMap;
+use crate::{
+    DECL_FN_NAME, EXCEPT_CLAUSE_NAME, GENERIC_PARAMS_CLAUSE_NAME, GET_BY_NAME_CLAUSE_NAME,
+    NAMES_CLAUSE_NAME, SELECT_VIS_CLAUSE_NAME, TAG_CLAUSE_NAME,
+};
 use syn::parse::{Parse, ParseStream, Result};
 use syn::token;
-use syn::{braced, Error, Generics, Ident, Token, Visibility, WhereClause};
+use syn::{braced, parenthesized, Error, Expr, Generics, Ident, Token, Visibility, WhereClause};
 
-use crate::parse_attribute::parse_inner_attributes;
-use crate::parse_gen_array::{parse_gen_arrays, GenArray};
+use crate::parse_attribute::{parse_inner_attributes, single_parse_outer_attribute};
+use crate::parse_gen_array::{check_cast_kind, parse_gen_arrays, GenArray};
 use crate::parse_in_array::{
-    parse_in_array_fields, InArrayElement, InArrayElementKind, InArrayField,
+    parse_in_array_named_fields, parse_in_array_unnamed_fields, InArrayElement, InArrayElementKind,
+    InArrayField,
 };
-use crate::types::ty_inferred_by;
+use crate::types::{is_type_param, mentions_generic_params, ty_inferred_by};
+use crate::utils::{make_suggestion, matches_name_pattern, member_to_string, ErrorAccumulator};
+
+const WRONG_ITEM_MESSAGE: &str =
+    "derive 'Arraygen' should only be used with braced or tuple structs, or with enums";
 
 pub struct DeriveArraygen {
-    pub gen_arrays: HashMap<Ident, GenArray>,
-    pub vis: Visibility,
+    pub gen_arrays: Vec<GenArray>,
     pub struct_name: Ident,
     pub generics: Generics,
+    pub variants: Option<Vec<Ident>>,
 }
 
 impl Parse for DeriveArraygen {
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut gen_arrays = input.call(parse_gen_arrays)?;
-        let vis = input.parse::<Visibility>()?;
+        let mut errors = None;
+        let result = parse_derive_arraygen(input, &mut errors);
+        errors.into_result(result)
+    }
+}
 
-        let lookahead = input.lookahead1();
-        if !lookahead.peek(Token![struct]) {
-            return Err(input.error("derive 'Arraygen' should only be used with braced structs"));
-        }
+fn parse_derive_arraygen(input: ParseStream, errors: &mut Option<Error>) -> Result<DeriveArraygen> {
+    let mut gen_arrays = parse_gen_arrays(input, errors)?;
+    let _ = input.parse::<Visibility>()?;
 
+    let lookahead = input.lookahead1();
+    let is_enum = if lookahead.peek(Token![struct]) {
         let _ = input.parse::<Token![struct]>()?;
-        let struct_name = input.parse::<Ident>()?;
-        let generics = input.parse::<Generics>()?;
-        let where_clause = parse_struct(input, &mut gen_arrays)?;
-
-        Ok(DeriveArraygen {
-            gen_arrays,
-            vis,
-            struct_name,
-            generics: Generics {
-                where_clause,
-                ..generics
-            },
-        })
+        false
+    } else if lookahead.peek(Token![enum]) {
+        let _ = input.parse::<Token![enum]>()?;
+        true
+    } else {
+        return Err(input.error(WRONG_ITEM_MESSAGE));
+    };
+
+    let struct_name = input.parse::<Ident>()?;
+    let generics = input.parse::<Generics>()?;
+    let type_params: Vec<Ident> = generics
+        .type_params()
+        .map(|type_param| type_param.ident.clone())
+        .collect();
+    if type_params.is_empty() {
+        for clause in gen_arrays
+            .iter()
+            .filter_map(|ga| ga.generic_params.as_ref())
+        {
+            errors.push_error(Error::new_spanned(
+                clause,
+                format!(
+                    "clause '{}' can't be used because '{}' has no type parameters",
+                    GENERIC_PARAMS_CLAUSE_NAME, struct_name
+                ),
+            ));
+        }
     }
+    let (where_clause, variants) = if is_enum {
+        for names_fn in gen_arrays.iter().filter_map(|ga| ga.names_fn.as_ref()) {
+            errors.push_error(Error::new_spanned(
+                names_fn,
+                format!("clause '{}' is not supported on enums", NAMES_CLAUSE_NAME),
+            ));
+        }
+        for vis in gen_arrays.iter().flat_map(|ga| ga.select_vis.iter()) {
+            errors.push_error(Error::new_spanned(
+                vis,
+                format!(
+                    "clause '{}' is not supported on enums, since their fields have no visibility",
+                    SELECT_VIS_CLAUSE_NAME
+                ),
+            ));
+        }
+        let (where_clause, variants) = parse_enum(input, &mut gen_arrays, &type_params, errors)?;
+        (where_clause, Some(variants))
+    } else {
+        (
+            parse_struct(input, &mut gen_arrays, &type_params, errors)?,
+            None,
+        )
+    };
+
+    check_excepted_fields(&gen_arrays, errors);
+    check_tagged_methods(&gen_arrays, errors);
+    if !is_enum {
+        check_flattened_fields(&gen_arrays, &generics, &struct_name, errors);
+    }
+
+    Ok(DeriveArraygen {
+        gen_arrays,
+        struct_name,
+        generics: Generics {
+            where_clause,
+            ..generics
+        },
+        variants,
+    })
 }
 
 pub fn parse_struct(
     input: ParseStream,
-    gen_arrays: &mut HashMap<Ident, GenArray>,
+    gen_arrays: &mut [GenArray],
+    type_params: &[Ident],
+    errors: &mut Option<Error>,
 ) -> Result<Option<WhereClause>> {
     let mut lookahead = input.lookahead1();
     let mut where_clause = None;
@@ -57,76 +124,395 @@ pub fn parse_struct(
     }
 
     if lookahead.peek(token::Brace) {
-        parse_braced_struct(input, gen_arrays)?;
+        parse_braced_struct(input, gen_arrays, None, type_params, errors)?;
+        Ok(where_clause)
+    } else if where_clause.is_none() && lookahead.peek(token::Paren) {
+        parse_tuple_struct(input, gen_arrays, None, type_params, errors)?;
+        if input.peek(Token![where]) {
+            where_clause = Some(input.parse()?);
+        }
+        let _ = input.parse::<Token![;]>()?;
         Ok(where_clause)
     } else {
-        Err(input.error("derive 'Arraygen' should only be used with braced structs"))
+        Err(input.error(WRONG_ITEM_MESSAGE))
     }
 }
 
+pub fn parse_enum(
+    input: ParseStream,
+    gen_arrays: &mut [GenArray],
+    type_params: &[Ident],
+    errors: &mut Option<Error>,
+) -> Result<(Option<WhereClause>, Vec<Ident>)> {
+    let mut where_clause = None;
+    if input.peek(Token![where]) {
+        where_clause = Some(input.parse()?);
+    }
+
+    let content;
+    let _ = braced!(content in input);
+    let mut variants = vec![];
+    while !content.is_empty() {
+        while content.peek(Token![#]) {
+            content.call(single_parse_outer_attribute)?;
+        }
+        let _ = content.parse::<Visibility>()?;
+        let variant = content.parse::<Ident>()?;
+        if content.peek(token::Brace) {
+            parse_braced_struct(&content, gen_arrays, Some(&variant), type_params, errors)?;
+        } else if content.peek(token::Paren) {
+            parse_tuple_struct(&content, gen_arrays, Some(&variant), type_params, errors)?;
+        }
+        if content.peek(Token![=]) {
+            let _ = content.parse::<Token![=]>()?;
+            let _ = content.parse::<Expr>()?;
+        }
+        variants.push(variant);
+        if content.is_empty() {
+            break;
+        }
+        let _ = content.parse::<Token![,]>()?;
+    }
+    Ok((where_clause, variants))
+}
+
 pub(crate) fn parse_braced_struct(
     input: ParseStream,
-    gen_arrays: &mut HashMap<Ident, GenArray>,
+    gen_arrays: &mut [GenArray],
+    variant: Option<&Ident>,
+    type_params: &[Ident],
+    errors: &mut Option<Error>,
 ) -> Result<()> {
     let content;
     let _ = braced!(content in input);
     parse_inner_attributes(&content)?;
-    for iaf in content
-        .parse_terminated::<InArrayField, Token![,]>(parse_in_array_fields)?
-        .into_iter()
-    {
-        for (_, ga) in gen_arrays.iter_mut() {
-            for implicit_ty in ga.implicit_select_all_tys.iter() {
-                if ty_inferred_by(&iaf.ty, implicit_ty) {
-                    ga.fields.push(InArrayElement {
-                        ident: iaf.ident.clone(),
-                        ty: iaf.ty.clone(),
-                        cast: ga.implicit_select_all_decorator.cast.clone(),
-                        kind: InArrayElementKind::Implicit,
-                    });
-                }
+    let fields = parse_in_array_named_fields(&content, errors)?;
+    select_fields(fields, gen_arrays, variant, type_params, errors);
+    Ok(())
+}
+
+pub(crate) fn parse_tuple_struct(
+    input: ParseStream,
+    gen_arrays: &mut [GenArray],
+    variant: Option<&Ident>,
+    type_params: &[Ident],
+    errors: &mut Option<Error>,
+) -> Result<()> {
+    let content;
+    let _ = parenthesized!(content in input);
+    let fields = parse_in_array_unnamed_fields(&content, errors)?;
+    select_fields(fields, gen_arrays, variant, type_params, errors);
+    Ok(())
+}
+
+fn select_fields(
+    fields: impl IntoIterator<Item = InArrayField>,
+    gen_arrays: &mut [GenArray],
+    variant: Option<&Ident>,
+    type_params: &[Ident],
+    errors: &mut Option<Error>,
+) {
+    let method_names: Vec<String> = gen_arrays.iter().map(|ga| ga.fn_name.to_string()).collect();
+    for iaf in fields {
+        for ga in gen_arrays.iter_mut() {
+            let mut is_excepted = false;
+            for excepted in ga
+                .implicit_select_all_except
+                .iter_mut()
+                .filter(|excepted| excepted.member == iaf.member)
+            {
+                excepted.found = true;
+                is_excepted = true;
+            }
+            let skip_entry = iaf
+                .attrs
+                .iter()
+                .flat_map(|attr| attr.entries.iter())
+                .find(|entry| entry.ident == ga.fn_name && entry.decorator.skip);
+
+            let name = member_to_string(&iaf.member);
+            let decorator = if ga
+                .implicit_select_all_tys
+                .iter()
+                .any(|implicit_ty| ty_inferred_by(&iaf.ty, implicit_ty))
+            {
+                Some(&ga.implicit_select_all_decorator)
+            } else if ga
+                .select_by_name_patterns
+                .iter()
+                .any(|pattern| matches_name_pattern(&pattern.value(), &name))
+            {
+                Some(&ga.select_by_name_decorator)
+            } else if ga
+                .select_vis
+                .iter()
+                .any(|vis| are_matching_visibilities(vis, &iaf.vis))
+            {
+                Some(&ga.select_vis_decorator)
+            } else if ga.generic_params.is_some() && is_type_param(&iaf.ty, type_params) {
+                Some(&ga.generic_params_decorator)
+            } else {
+                None
+            };
+            if let (Some(skip_entry), None) = (skip_entry, decorator) {
+                errors.push_error(Error::new_spanned(
+                    &skip_entry.ident,
+                    format!(
+                        "Field '{}' is not implicitly selected by {} method '{}'",
+                        name, DECL_FN_NAME, ga.fn_name
+                    ),
+                ));
+            }
+            if is_excepted || skip_entry.is_some() {
+                continue;
+            }
+            if let Some(decorator) = decorator {
+                let cast = decorator.cast.clone();
+                ga.fields.push(InArrayElement {
+                    variant: variant.cloned(),
+                    member: iaf.member.clone(),
+                    ty: iaf.ty.clone(),
+                    cast,
+                    flatten: false,
+                    kind: InArrayElementKind::Implicit,
+                });
             }
         }
         for attr in iaf.attrs.iter() {
             for entry in attr.entries.iter() {
-                if let Some(ga) = gen_arrays.get_mut(&entry.ident) {
-                    let iae = ga.fields.iter().find(|iae| iae.ident == iaf.ident);
+                if let Some(ga) = gen_arrays.iter_mut().find(|ga| ga.fn_name == entry.ident) {
+                    if entry.decorator.skip {
+                        continue;
+                    }
+
+                    let iae = ga
+                        .fields
+                        .iter()
+                        .find(|iae| iae.member == iaf.member && iae.variant.as_ref() == variant);
                     if matches!(iae, Some(iae) if iae.kind != InArrayElementKind::Implicit || (iae.kind == InArrayElementKind::Implicit && !entry.decorator.override_implicit))
                     {
-                        return Err(Error::new_spanned(
+                        errors.push_error(Error::new_spanned(
                             entry.ident.clone(),
                             format!(
                                 "Field '{}' is already included in {} method '{}'",
-                                iaf.ident.to_string(),
+                                member_to_string(&iaf.member),
                                 DECL_FN_NAME,
-                                entry.ident.to_string()
+                                entry.ident
                             ),
                         ));
                     } else {
-                        if let Some(ident) = iae.map(|iae| iae.ident.clone()) {
-                            ga.fields.retain(|field| field.ident != ident);
+                        if let Some(member) = iae.map(|iae| iae.member.clone()) {
+                            ga.fields.retain(|field| {
+                                field.member != member || field.variant.as_ref() != variant
+                            });
+                        }
+
+                        if let Some(error) =
+                            check_cast_kind(&entry.ident, entry.decorator.cast.as_ref(), ga)
+                        {
+                            errors.push_error(error);
+                        }
+
+                        for (clause, clause_fn) in [
+                            (NAMES_CLAUSE_NAME, &ga.names_fn),
+                            (GET_BY_NAME_CLAUSE_NAME, &ga.get_by_name_fn),
+                        ] {
+                            if let (true, Some(clause_fn)) = (entry.decorator.flatten, clause_fn) {
+                                errors.push_error(Error::new_spanned(
+                                    entry.ident.clone(),
+                                    format!(
+                                        "decorator 'flatten' can't be used in {} method '{}' because it contains {} clause '{}'",
+                                        DECL_FN_NAME, ga.fn_name, clause, clause_fn
+                                    ),
+                                ));
+                            }
                         }
 
                         ga.fields.push(InArrayElement {
-                            ident: iaf.ident.clone(),
+                            variant: variant.cloned(),
+                            member: iaf.member.clone(),
                             ty: iaf.ty.clone(),
                             cast: entry.decorator.cast.clone(),
+                            flatten: entry.decorator.flatten,
                             kind: InArrayElementKind::InArray,
                         });
                     }
                 } else {
-                    return Err(Error::new_spanned(
+                    errors.push_error(Error::new_spanned(
                         entry.ident.clone(),
                         format!(
-                            "{} method '{}' not present but used by field '{}'",
+                            "{} method '{}' not present but used by field '{}'{}",
                             DECL_FN_NAME,
-                            entry.ident.to_string(),
-                            iaf.ident.to_string()
+                            entry.ident,
+                            member_to_string(&iaf.member),
+                            make_suggestion(
+                                &entry.ident.to_string(),
+                                method_names.iter().map(String::as_str),
+                                "methods"
+                            )
                         ),
                     ));
                 }
             }
         }
     }
-    Ok(())
+}
+
+fn are_matching_visibilities(left: &Visibility, right: &Visibility) -> bool {
+    match (left, right) {
+        (Visibility::Public(_), Visibility::Public(_)) => true,
+        (Visibility::Inherited, Visibility::Inherited) => true,
+        _ => matches!(
+            (restricted_path_segments(left), restricted_path_segments(right)),
+            (Some(left), Some(right)) if left == right
+        ),
+    }
+}
+
+fn restricted_path_segments(vis: &Visibility) -> Option<Vec<String>> {
+    match vis {
+        Visibility::Crate(_) => Some(vec!["crate".to_string()]),
+        Visibility::Restricted(restricted) => Some(
+            restricted
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn check_excepted_fields(gen_arrays: &[GenArray], errors: &mut Option<Error>) {
+    for ga in gen_arrays.iter() {
+        for excepted in ga.implicit_select_all_except.iter() {
+            if !excepted.found {
+                errors.push_error(Error::new_spanned(
+                    &excepted.member,
+                    format!(
+                        "Field '{}' excluded by {} clause of {} method '{}' is not present",
+                        member_to_string(&excepted.member),
+                        EXCEPT_CLAUSE_NAME,
+                        DECL_FN_NAME,
+                        ga.fn_name
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+// The length of the array of structs includes the length of the flattened types, and array lengths
+// can't depend on generic parameters.
+fn check_flattened_fields(
+    gen_arrays: &[GenArray],
+    generics: &Generics,
+    struct_name: &Ident,
+    errors: &mut Option<Error>,
+) {
+    let params: Vec<Ident> = generics
+        .type_params()
+        .map(|type_param| type_param.ident.clone())
+        .chain(
+            generics
+                .const_params()
+                .map(|const_param| const_param.ident.clone()),
+        )
+        .collect();
+    for ga in gen_arrays.iter() {
+        for iae in ga
+            .fields
+            .iter()
+            .filter(|iae| iae.flatten && mentions_generic_params(&iae.ty, &params))
+        {
+            errors.push_error(Error::new_spanned(
+                &iae.ty,
+                format!(
+                    "decorator 'flatten' can't be used in {} method '{}' on field '{}' because its type depends on the generic parameters of '{}'",
+                    DECL_FN_NAME,
+                    ga.fn_name,
+                    member_to_string(&iae.member),
+                    struct_name
+                ),
+            ));
+        }
+    }
+}
+
+fn check_tagged_methods(gen_arrays: &[GenArray], errors: &mut Option<Error>) {
+    for ga in gen_arrays.iter().filter(|ga| ga.is_fallible()) {
+        if let Some(tag) = &ga.tag {
+            errors.push_error(Error::new_spanned(
+                tag,
+                format!(
+                    "{} method '{}' can't contain {} clause because it returns a Result",
+                    DECL_FN_NAME, ga.fn_name, TAG_CLAUSE_NAME
+                ),
+            ));
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn vis(source: &str) -> Visibility {
+        syn::parse_str(source).unwrap()
+    }
+
+    #[test]
+    fn are_matching_visibilities___with_same_visibility___matches() {
+        for source in [
+            "pub",
+            "",
+            "pub(crate)",
+            "pub(super)",
+            "pub(self)",
+            "pub(in a::b)",
+        ] {
+            assert!(
+                are_matching_visibilities(&vis(source), &vis(source)),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn are_matching_visibilities___with_equivalent_spellings___matches() {
+        assert!(are_matching_visibilities(&vis("crate"), &vis("pub(crate)")));
+        assert!(are_matching_visibilities(
+            &vis("pub(in crate)"),
+            &vis("pub(crate)")
+        ));
+        assert!(are_matching_visibilities(
+            &vis("pub(in super)"),
+            &vis("pub(super)")
+        ));
+        assert!(are_matching_visibilities(
+            &vis("pub ( in a :: b )"),
+            &vis("pub(in a::b)")
+        ));
+    }
+
+    #[test]
+    fn are_matching_visibilities___with_different_visibility___does_not_match() {
+        assert!(!are_matching_visibilities(&vis("pub"), &vis("pub(crate)")));
+        assert!(!are_matching_visibilities(&vis(""), &vis("pub(self)")));
+        assert!(!are_matching_visibilities(
+            &vis("pub(crate)"),
+            &vis("pub(super)")
+        ));
+        assert!(!are_matching_visibilities(
+            &vis("pub(in a::b)"),
+            &vis("pub(in a)")
+        ));
+        assert!(!are_matching_visibilities(
+            &vis("pub(in a::b)"),
+            &vis("pub(in a::c)")
+        ));
+    }
 }
@@ -1,14 +1,42 @@
 use quote::quote;
-use std::collections::HashMap;
-use syn::parse::{Parse, ParseStream, Result};
+use syn::parse::{ParseStream, Result};
 use syn::token;
-use syn::{bracketed, parenthesized, Error, Ident, Path, Token, Type, Visibility};
+use syn::{bracketed, parenthesized, Error, Ident, LitStr, Member, Path, Token, Type, Visibility};
 
 use crate::parse_attribute::single_parse_outer_attribute;
 use crate::parse_decorator::{CastKind, Decorator};
 use crate::parse_in_array::InArrayElement;
-use crate::types::are_matching_types;
-use crate::{DECL_FN_NAME, IMPLICIT_SELECT_ALL_NAME};
+use crate::types::{are_matching_types, parse_type_pattern, type_pattern_to_string};
+use crate::utils::{make_suggestion, ErrorAccumulator};
+use crate::{
+    ALLOW_EMPTY_CLAUSE_NAME, BY_VALUE_CLAUSE_NAME, DECL_FN_NAME, EXCEPT_CLAUSE_NAME,
+    GENERIC_PARAMS_CLAUSE_NAME, GET_BY_NAME_CLAUSE_NAME, GET_CLAUSE_NAME, IMPLICIT_SELECT_ALL_NAME,
+    IMPL_TRAIT_CLAUSE_NAME, NAMES_CLAUSE_NAME, SELECT_BY_NAME_CLAUSE_NAME, SELECT_VIS_CLAUSE_NAME,
+    TAG_CLAUSE_NAME, WITH_NAMES_CLAUSE_NAME,
+};
+
+const CLAUSE_NAMES: &[&str] = &[
+    IMPLICIT_SELECT_ALL_NAME,
+    NAMES_CLAUSE_NAME,
+    WITH_NAMES_CLAUSE_NAME,
+    BY_VALUE_CLAUSE_NAME,
+    ALLOW_EMPTY_CLAUSE_NAME,
+    EXCEPT_CLAUSE_NAME,
+    SELECT_BY_NAME_CLAUSE_NAME,
+    SELECT_VIS_CLAUSE_NAME,
+    GENERIC_PARAMS_CLAUSE_NAME,
+    TAG_CLAUSE_NAME,
+    IMPL_TRAIT_CLAUSE_NAME,
+    GET_CLAUSE_NAME,
+    GET_BY_NAME_CLAUSE_NAME,
+];
+
+const FLAG_CLAUSE_NAMES: &[&str] = &[
+    WITH_NAMES_CLAUSE_NAME,
+    BY_VALUE_CLAUSE_NAME,
+    ALLOW_EMPTY_CLAUSE_NAME,
+    GENERIC_PARAMS_CLAUSE_NAME,
+];
 
 pub struct GenArray {
     pub vis: Visibility,
@@ -18,16 +46,61 @@ pub struct GenArray {
     pub is_ref: bool,
     pub implicit_select_all_tys: Vec<Type>,
     pub implicit_select_all_decorator: Decorator,
-    pub casts: Vec<(syn::Ident, syn::Type, proc_macro2::TokenStream, CastKind)>,
+    pub implicit_select_all_except: Vec<ExceptedField>,
+    pub select_by_name_patterns: Vec<LitStr>,
+    pub select_by_name_decorator: Decorator,
+    pub select_vis: Vec<Visibility>,
+    pub select_vis_decorator: Decorator,
+    pub generic_params: Option<Ident>,
+    pub generic_params_decorator: Decorator,
+    pub names_fn: Option<Ident>,
+    pub get_fn: Option<Ident>,
+    pub get_by_name_fn: Option<Ident>,
+    pub tag: Option<Path>,
+    pub impl_trait: Option<Path>,
+    pub with_names: bool,
+    pub by_value: bool,
+    pub allow_empty: bool,
     pub fields: Vec<InArrayElement>,
 }
 
-pub fn parse_gen_arrays(input: ParseStream) -> Result<HashMap<Ident, GenArray>> {
-    let mut gen_arrays = HashMap::new();
+impl GenArray {
+    // Methods with elements using the 'try_into' decorator return a Result, so conversion errors
+    // can be propagated with the '?' operator.
+    pub fn is_fallible(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|iae| iae.cast == Some(CastKind::TryInto))
+    }
+}
+
+pub struct ExceptedField {
+    pub member: Member,
+    pub found: bool,
+}
+
+pub fn parse_gen_arrays(input: ParseStream, errors: &mut Option<Error>) -> Result<Vec<GenArray>> {
+    let mut gen_arrays: Vec<GenArray> = vec![];
     while input.peek(Token![#]) {
         if let Ok(true) = is_gen_array(&input.fork()) {
-            let gen_array = input.call(single_parse_gen_array)?;
-            gen_arrays.insert(gen_array.fn_name.clone(), gen_array);
+            let gen_array = match single_parse_gen_array(input, errors) {
+                Ok(gen_array) => gen_array,
+                Err(error) => {
+                    errors.push_error(error);
+                    continue;
+                }
+            };
+            if gen_arrays.iter().any(|ga| ga.fn_name == gen_array.fn_name) {
+                errors.push_error(Error::new_spanned(
+                    &gen_array.fn_name,
+                    format!(
+                        "{} method '{}' is declared more than once",
+                        DECL_FN_NAME, gen_array.fn_name
+                    ),
+                ));
+                continue;
+            }
+            gen_arrays.push(gen_array);
         } else {
             input.call(single_parse_outer_attribute)?;
         }
@@ -35,7 +108,7 @@ pub fn parse_gen_arrays(input: ParseStream) -> Result<HashMap<Ident, GenArray>>
     Ok(gen_arrays)
 }
 
-pub fn single_parse_gen_array(input: ParseStream) -> Result<GenArray> {
+pub fn single_parse_gen_array(input: ParseStream, errors: &mut Option<Error>) -> Result<GenArray> {
     let content;
     let _: Token![#] = input.parse()?;
     let _: token::Bracket = bracketed!(content in input);
@@ -45,7 +118,7 @@ pub fn single_parse_gen_array(input: ParseStream) -> Result<GenArray> {
         return Err(input.error(format!("Wrong syntax for {}", DECL_FN_NAME)));
     }
 
-    content.call(parse_gen_array_group)
+    parse_gen_array_group(&content, errors)
 }
 
 pub fn is_gen_array(input: ParseStream) -> Result<bool> {
@@ -57,7 +130,7 @@ pub fn is_gen_array(input: ParseStream) -> Result<bool> {
     Ok(!path.segments.is_empty() && path.segments[0].ident == DECL_FN_NAME)
 }
 
-pub fn parse_gen_array_group(input: ParseStream) -> Result<GenArray> {
+pub fn parse_gen_array_group(input: ParseStream, errors: &mut Option<Error>) -> Result<GenArray> {
     let content;
     let _ = parenthesized!(content in input);
     let vis: Visibility = content.parse()?;
@@ -72,66 +145,390 @@ pub fn parse_gen_array_group(input: ParseStream) -> Result<GenArray> {
         (false, false)
     };
 
-    let mut implicit_select_all_tys = vec![];
-    let mut implicit_select_all_decorator = Decorator::new();
+    let mut gen_array = GenArray {
+        vis,
+        fn_name,
+        fn_ty,
+        is_mut,
+        is_ref,
+        implicit_select_all_tys: vec![],
+        implicit_select_all_decorator: Decorator::new(),
+        implicit_select_all_except: vec![],
+        select_by_name_patterns: vec![],
+        select_by_name_decorator: Decorator::new(),
+        select_vis: vec![],
+        select_vis_decorator: Decorator::new(),
+        generic_params: None,
+        generic_params_decorator: Decorator::new(),
+        names_fn: None,
+        get_fn: None,
+        get_by_name_fn: None,
+        tag: None,
+        impl_trait: None,
+        with_names: false,
+        by_value: false,
+        allow_empty: false,
+        fields: vec![],
+    };
+
+    if let Err(error) = parse_gen_array_clauses(&content, &mut gen_array, errors) {
+        errors.push_error(error);
+    }
+
+    Ok(gen_array)
+}
 
-    if content.peek(Token![,]) && content.peek2(syn::Ident) {
-        let _: Token![,] = content.parse()?;
-        let implicit: syn::Ident = content.parse()?;
-        if implicit != IMPLICIT_SELECT_ALL_NAME {
-            return Err(content.error(format!("clause '{}' not recognised", implicit)));
+fn parse_gen_array_clauses(
+    input: ParseStream,
+    gen_array: &mut GenArray,
+    errors: &mut Option<Error>,
+) -> Result<()> {
+    let fn_name = gen_array.fn_name.clone();
+    let mut except_clause = None;
+    let mut declared_clauses: Vec<String> = vec![];
+    while input.peek(Token![,]) {
+        let _: Token![,] = input.parse()?;
+        if input.is_empty() {
+            break;
         }
 
-        implicit_select_all_decorator = content.parse::<Decorator>()?;
-        if implicit_select_all_decorator.override_implicit {
-            return Err(Error::new_spanned(
-                implicit,
-                format!(
-                    "{} method '{}' contains {} clause with forbidden decorator 'override_implicit'",
-                    DECL_FN_NAME,
-                    fn_name,
-                    IMPLICIT_SELECT_ALL_NAME
-                ),
-            ));
+        let clause: Ident = input.parse()?;
+        let clause_name = clause.to_string();
+        if CLAUSE_NAMES.contains(&clause_name.as_ref()) {
+            if declared_clauses.contains(&clause_name) {
+                errors.push_error(Error::new_spanned(
+                    &clause,
+                    format!("clause '{}' is declared more than once", clause_name),
+                ));
+            } else {
+                declared_clauses.push(clause_name);
+            }
         }
 
-        let _: Token![:] = content.parse::<Token![:]>()?;
-        implicit_select_all_tys = content
-            .parse_terminated::<Type, Token![,]>(Type::parse)?
-            .into_iter()
-            .collect::<Vec<Type>>();
+        match clause.to_string().as_ref() {
+            IMPLICIT_SELECT_ALL_NAME => {
+                gen_array.implicit_select_all_decorator = input.parse::<Decorator>()?;
+                check_forbidden_decorators(
+                    &clause,
+                    &gen_array.implicit_select_all_decorator,
+                    &fn_name,
+                    errors,
+                );
 
-        if implicit_select_all_tys.is_empty() {
-            return Err(content.error("missing type to select"));
-        }
+                if let Some(error) = check_cast_kind(
+                    &clause,
+                    gen_array.implicit_select_all_decorator.cast.as_ref(),
+                    gen_array,
+                ) {
+                    errors.push_error(error);
+                }
 
-        for (i, ty_left) in implicit_select_all_tys.iter().enumerate() {
-            for ty_right in implicit_select_all_tys.iter().skip(i + 1) {
-                if are_matching_types(ty_left, ty_right) {
-                    return Err(Error::new_spanned(
-                        ty_right,
+                let _: Token![:] = input.parse::<Token![:]>()?;
+                gen_array.implicit_select_all_tys = parse_implicit_select_all_tys(input)?;
+
+                let mut distinct_tys: Vec<&Type> = vec![];
+                for ty_right in gen_array.implicit_select_all_tys.iter() {
+                    if !distinct_tys
+                        .iter()
+                        .any(|ty_left| are_matching_types(ty_left, ty_right))
+                    {
+                        distinct_tys.push(ty_right);
+                    } else {
+                        errors.push_error(Error::new_spanned(
+                            ty_right,
+                            format!(
+                                "{} method '{}' contains {} clause with duplicated '{}' type",
+                                DECL_FN_NAME,
+                                fn_name,
+                                IMPLICIT_SELECT_ALL_NAME,
+                                type_pattern_to_string(ty_right)
+                            ),
+                        ));
+                    }
+                }
+            }
+            NAMES_CLAUSE_NAME => {
+                let _: Token![=] = input.parse()?;
+                gen_array.names_fn = Some(input.parse::<Ident>()?);
+            }
+            GET_CLAUSE_NAME => {
+                let _: Token![=] = input.parse()?;
+                gen_array.get_fn = Some(input.parse::<Ident>()?);
+            }
+            GET_BY_NAME_CLAUSE_NAME => {
+                let _: Token![=] = input.parse()?;
+                gen_array.get_by_name_fn = Some(input.parse::<Ident>()?);
+            }
+            TAG_CLAUSE_NAME => {
+                let _: Token![=] = input.parse()?;
+                gen_array.tag = Some(input.parse::<Path>()?);
+            }
+            IMPL_TRAIT_CLAUSE_NAME => {
+                let _: Token![=] = input.parse()?;
+                let impl_trait = input.parse::<Path>()?;
+                if !matches!(gen_array.vis, Visibility::Inherited) {
+                    errors.push_error(Error::new_spanned(
+                        &gen_array.vis,
                         format!(
-                            "{} method '{}' contains {} clause with duplicated '{}' type",
+                            "{} method '{}' can't have visibility because it implements trait '{}'",
                             DECL_FN_NAME,
                             fn_name,
-                            IMPLICIT_SELECT_ALL_NAME,
-                            quote! { #ty_right }.to_string()
+                            quote! { #impl_trait }.to_string().replace(' ', "")
+                        ),
+                    ));
+                }
+                gen_array.impl_trait = Some(impl_trait);
+            }
+            WITH_NAMES_CLAUSE_NAME => gen_array.with_names = true,
+            BY_VALUE_CLAUSE_NAME => {
+                if gen_array.is_ref {
+                    errors.push_error(Error::new_spanned(
+                        &clause,
+                        format!(
+                            "{} method '{}' can't contain {} clause when returning references",
+                            DECL_FN_NAME, fn_name, BY_VALUE_CLAUSE_NAME
                         ),
                     ));
+                } else {
+                    gen_array.by_value = true
+                }
+            }
+            ALLOW_EMPTY_CLAUSE_NAME => gen_array.allow_empty = true,
+            SELECT_BY_NAME_CLAUSE_NAME => {
+                gen_array.select_by_name_decorator = input.parse::<Decorator>()?;
+                check_forbidden_decorators(
+                    &clause,
+                    &gen_array.select_by_name_decorator,
+                    &fn_name,
+                    errors,
+                );
+                if let Some(error) = check_cast_kind(
+                    &clause,
+                    gen_array.select_by_name_decorator.cast.as_ref(),
+                    gen_array,
+                ) {
+                    errors.push_error(error);
                 }
+
+                let _: Token![:] = input.parse()?;
+                gen_array.select_by_name_patterns = parse_name_patterns(input)?;
+            }
+            SELECT_VIS_CLAUSE_NAME => {
+                gen_array.select_vis_decorator = input.parse::<Decorator>()?;
+                check_forbidden_decorators(
+                    &clause,
+                    &gen_array.select_vis_decorator,
+                    &fn_name,
+                    errors,
+                );
+                if let Some(error) = check_cast_kind(
+                    &clause,
+                    gen_array.select_vis_decorator.cast.as_ref(),
+                    gen_array,
+                ) {
+                    errors.push_error(error);
+                }
+
+                let _: Token![:] = input.parse()?;
+                gen_array.select_vis = parse_visibilities(input)?;
+            }
+            GENERIC_PARAMS_CLAUSE_NAME => {
+                gen_array.generic_params_decorator = input.parse::<Decorator>()?;
+                check_forbidden_decorators(
+                    &clause,
+                    &gen_array.generic_params_decorator,
+                    &fn_name,
+                    errors,
+                );
+                if let Some(error) = check_cast_kind(
+                    &clause,
+                    gen_array.generic_params_decorator.cast.as_ref(),
+                    gen_array,
+                ) {
+                    errors.push_error(error);
+                }
+
+                gen_array.generic_params = Some(clause);
+            }
+            EXCEPT_CLAUSE_NAME => {
+                let _: Token![:] = input.parse()?;
+                gen_array.implicit_select_all_except = parse_except_members(input)?
+                    .into_iter()
+                    .map(|member| ExceptedField {
+                        member,
+                        found: false,
+                    })
+                    .collect();
+                except_clause = Some(clause);
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    &clause,
+                    format!(
+                        "clause '{}' not recognised{}",
+                        clause,
+                        make_suggestion(
+                            &clause.to_string(),
+                            CLAUSE_NAMES.iter().copied(),
+                            "clauses"
+                        )
+                    ),
+                ))
             }
         }
     }
 
-    Ok(GenArray {
-        vis,
-        fn_name,
-        fn_ty,
-        is_mut,
-        is_ref,
-        implicit_select_all_tys,
-        implicit_select_all_decorator,
-        casts: vec![],
-        fields: vec![],
-    })
+    match except_clause {
+        Some(except_clause)
+            if gen_array.implicit_select_all_tys.is_empty()
+                && gen_array.select_by_name_patterns.is_empty()
+                && gen_array.select_vis.is_empty()
+                && gen_array.generic_params.is_none() =>
+        {
+            errors.push_error(Error::new_spanned(
+                &except_clause,
+                format!(
+                    "{} method '{}' contains {} clause without {}, {}, {} or {} clauses",
+                    DECL_FN_NAME,
+                    fn_name,
+                    EXCEPT_CLAUSE_NAME,
+                    IMPLICIT_SELECT_ALL_NAME,
+                    SELECT_BY_NAME_CLAUSE_NAME,
+                    SELECT_VIS_CLAUSE_NAME,
+                    GENERIC_PARAMS_CLAUSE_NAME
+                ),
+            ));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn check_forbidden_decorators(
+    clause: &Ident,
+    decorator: &Decorator,
+    fn_name: &Ident,
+    errors: &mut Option<Error>,
+) {
+    for (forbidden, is_present) in [
+        ("override_implicit", decorator.override_implicit),
+        ("skip", decorator.skip),
+        ("flatten", decorator.flatten),
+    ] {
+        if is_present {
+            errors.push_error(Error::new_spanned(
+                clause,
+                format!(
+                    "{} method '{}' contains {} clause with forbidden decorator '{}'",
+                    DECL_FN_NAME, fn_name, clause, forbidden
+                ),
+            ));
+        }
+    }
+}
+
+pub fn check_cast_kind(
+    spanned: &Ident,
+    cast: Option<&CastKind>,
+    gen_array: &GenArray,
+) -> Option<Error> {
+    match cast {
+        Some(cast) if gen_array.is_ref && cast.returns_owned_value() => Some(Error::new_spanned(
+            spanned,
+            format!(
+                "decorator '{}' can't be used in {} method '{}' because it returns references",
+                cast.name(),
+                DECL_FN_NAME,
+                gen_array.fn_name
+            ),
+        )),
+        _ => None,
+    }
+}
+
+fn parse_implicit_select_all_tys(input: ParseStream) -> Result<Vec<Type>> {
+    if input.is_empty() || is_clause_start(input) {
+        return Err(input.error("missing type to select"));
+    }
+
+    let mut tys = vec![input.call(parse_type_pattern)?];
+    while input.peek(Token![,]) && !is_clause_start(input) {
+        let _: Token![,] = input.parse()?;
+        if input.is_empty() {
+            break;
+        }
+        tys.push(input.call(parse_type_pattern)?);
+    }
+    Ok(tys)
+}
+
+fn parse_except_members(input: ParseStream) -> Result<Vec<Member>> {
+    if input.is_empty() || is_clause_start(input) {
+        return Err(input.error("missing field to exclude"));
+    }
+
+    let mut members = vec![input.parse::<Member>()?];
+    while input.peek(Token![,]) && !is_clause_start(input) {
+        let _: Token![,] = input.parse()?;
+        if input.is_empty() {
+            break;
+        }
+        members.push(input.parse::<Member>()?);
+    }
+    Ok(members)
+}
+
+fn parse_name_patterns(input: ParseStream) -> Result<Vec<LitStr>> {
+    if input.is_empty() || is_clause_start(input) {
+        return Err(input.error("missing name pattern to select"));
+    }
+
+    let mut patterns = vec![input.parse::<LitStr>()?];
+    while input.peek(Token![,]) && !is_clause_start(input) {
+        let _: Token![,] = input.parse()?;
+        if input.is_empty() {
+            break;
+        }
+        patterns.push(input.parse::<LitStr>()?);
+    }
+    for pattern in patterns.iter() {
+        if pattern.value().is_empty() {
+            return Err(Error::new_spanned(pattern, "name pattern can't be empty"));
+        }
+    }
+    Ok(patterns)
+}
+
+fn parse_visibilities(input: ParseStream) -> Result<Vec<Visibility>> {
+    let mut visibilities = vec![];
+    loop {
+        let vis: Visibility = input.parse()?;
+        if let Visibility::Inherited = vis {
+            return Err(input.error("missing visibility to select"));
+        }
+        visibilities.push(vis);
+        if !input.peek(Token![,]) || is_clause_start(input) {
+            break;
+        }
+        let _: Token![,] = input.parse()?;
+        if input.is_empty() {
+            break;
+        }
+    }
+    Ok(visibilities)
+}
+
+fn is_clause_start(input: ParseStream) -> bool {
+    let fork = input.fork();
+    if fork.peek(Token![,]) {
+        let _ = fork.parse::<Token![,]>();
+    }
+    let ident = match fork.parse::<Ident>() {
+        Ok(ident) => ident,
+        Err(_) => return false,
+    };
+    FLAG_CLAUSE_NAMES.iter().any(|flag| ident == flag)
+        || fork.peek(Token![=])
+        || fork.peek(token::Brace)
+        || (fork.peek(Token![:]) && !fork.peek(Token![::]))
 }
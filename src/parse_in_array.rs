@@ -1,9 +1,11 @@
 use syn::parse::{ParseStream, Result};
+use syn::spanned::Spanned;
 use syn::token;
-use syn::{bracketed, parenthesized, Ident, Path, Token, Type, Visibility};
+use syn::{bracketed, parenthesized, Error, Ident, Index, Member, Path, Token, Type, Visibility};
 
 use crate::parse_attribute::single_parse_outer_attribute;
 use crate::parse_decorator::{CastKind, Decorator};
+use crate::utils::ErrorAccumulator;
 use crate::FIELD_SELECTOR_NAME;
 
 #[derive(PartialEq)]
@@ -13,9 +15,11 @@ pub enum InArrayElementKind {
 }
 
 pub struct InArrayElement {
-    pub ident: Ident,
+    pub variant: Option<Ident>,
+    pub member: Member,
     pub ty: Type,
     pub cast: Option<CastKind>,
+    pub flatten: bool,
     pub kind: InArrayElementKind,
 }
 
@@ -30,17 +34,60 @@ pub struct InArrayAttribute {
 
 pub struct InArrayField {
     pub attrs: Vec<InArrayAttribute>,
-    pub ident: Ident,
+    pub vis: Visibility,
+    pub member: Member,
     pub ty: Type,
 }
 
-pub fn parse_in_array_fields(input: ParseStream) -> Result<InArrayField> {
-    let attrs: Vec<InArrayAttribute> = input.call(parse_in_array_attributes)?;
-    let _: Visibility = input.parse()?;
-    let ident: Ident = input.parse()?;
-    let _: Option<token::Colon> = Some(input.parse()?);
-    let ty: Type = input.parse()?;
-    Ok(InArrayField { attrs, ident, ty })
+pub fn parse_in_array_named_fields(
+    input: ParseStream,
+    errors: &mut Option<Error>,
+) -> Result<Vec<InArrayField>> {
+    let mut fields = vec![];
+    while !input.is_empty() {
+        let attrs: Vec<InArrayAttribute> = parse_in_array_attributes(input, errors)?;
+        let vis: Visibility = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let _: Option<token::Colon> = Some(input.parse()?);
+        let ty: Type = input.parse()?;
+        fields.push(InArrayField {
+            attrs,
+            vis,
+            member: Member::Named(ident),
+            ty,
+        });
+        if input.is_empty() {
+            break;
+        }
+        let _: Token![,] = input.parse()?;
+    }
+    Ok(fields)
+}
+
+pub fn parse_in_array_unnamed_fields(
+    input: ParseStream,
+    errors: &mut Option<Error>,
+) -> Result<Vec<InArrayField>> {
+    let mut fields = vec![];
+    while !input.is_empty() {
+        let attrs: Vec<InArrayAttribute> = parse_in_array_attributes(input, errors)?;
+        let vis: Visibility = input.parse()?;
+        let ty: Type = input.parse()?;
+        fields.push(InArrayField {
+            attrs,
+            vis,
+            member: Member::Unnamed(Index {
+                index: fields.len() as u32,
+                span: ty.span(),
+            }),
+            ty,
+        });
+        if input.is_empty() {
+            break;
+        }
+        let _: Token![,] = input.parse()?;
+    }
+    Ok(fields)
 }
 
 pub fn is_in_array_attribute(input: ParseStream) -> Result<bool> {
@@ -52,11 +99,17 @@ pub fn is_in_array_attribute(input: ParseStream) -> Result<bool> {
     Ok(!path.segments.is_empty() && path.segments[0].ident == FIELD_SELECTOR_NAME)
 }
 
-pub fn parse_in_array_attributes(input: ParseStream) -> Result<Vec<InArrayAttribute>> {
+pub fn parse_in_array_attributes(
+    input: ParseStream,
+    errors: &mut Option<Error>,
+) -> Result<Vec<InArrayAttribute>> {
     let mut ret = vec![];
     while input.peek(Token![#]) {
         if let Ok(true) = is_in_array_attribute(&input.fork()) {
-            ret.push(input.call(parse_single_in_array_attribute_header)?);
+            match input.call(parse_single_in_array_attribute_header) {
+                Ok(attribute) => ret.push(attribute),
+                Err(error) => errors.push_error(error),
+            }
         } else {
             input.call(single_parse_outer_attribute)?;
         }
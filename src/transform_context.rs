@@ -1,32 +1,37 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenTree;
-use quote::quote;
-use std::collections::HashMap;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
 use syn::parse_macro_input;
-use syn::{Ident, Type};
+use syn::{Generics, Ident, Lifetime, Type};
 
 use crate::parse_decorator::CastKind;
 use crate::parse_derive_arraygen::DeriveArraygen;
 use crate::parse_gen_array::GenArray;
-use crate::{DECL_FN_NAME, DERIVE_NAME};
+use crate::parse_in_array::InArrayElement;
+use crate::types::{erase_lifetimes, name_elided_lifetimes};
+use crate::utils::member_to_string;
+use crate::{ALLOW_EMPTY_CLAUSE_NAME, DECL_FN_NAME, DERIVE_NAME};
 
 pub fn transform_ast(input: TokenStream) -> TokenStream {
     let arraygen = parse_macro_input!(input as DeriveArraygen);
 
     let struct_name = arraygen.struct_name;
 
-    if arraygen.gen_arrays.is_empty() {
-        eprintln!(
-            //struct_name.span(), @TODO emit warning
-            "warning (Arraygen): The struct '{}' derives '{}' but does not contain any '{}' attribute, so '{}' does nothing.",
-            struct_name,
-            DERIVE_NAME,
-            DECL_FN_NAME,
-            DERIVE_NAME
-        );
-    }
-
-    let impl_fns = make_impl_fns(arraygen.gen_arrays, &struct_name);
+    let warnings = make_warnings(&arraygen.gen_arrays, &struct_name, &arraygen.variants);
+    let trait_impls = make_trait_impls(
+        &arraygen.gen_arrays,
+        &struct_name,
+        &arraygen.generics,
+        arraygen.variants.is_some(),
+    );
+    let impl_trait_impls = make_impl_trait_impls(
+        &arraygen.gen_arrays,
+        &struct_name,
+        &arraygen.generics,
+        &arraygen.variants,
+    );
+    let impl_fns = make_impl_fns(arraygen.gen_arrays, &arraygen.variants);
     let (impl_generics, ty_generics, where_clause) = arraygen.generics.split_for_impl();
 
     let tokens = quote! {
@@ -36,35 +41,732 @@ pub fn transform_ast(input: TokenStream) -> TokenStream {
             #(#impl_fns)
             *
         }
+
+        #(#impl_trait_impls)*
+
+        #(#trait_impls)*
+
+        #(#warnings)*
     };
 
     tokens.into()
 }
 
-fn make_impl_fns(methods: HashMap<Ident, GenArray>, struct_name: &Ident) -> Vec<TokenTree> {
+fn make_warnings(
+    methods: &[GenArray],
+    struct_name: &Ident,
+    variants: &Option<Vec<Ident>>,
+) -> Vec<proc_macro2::TokenStream> {
+    let (item_kind, collection_kind) = if variants.is_some() {
+        ("enum", "list")
+    } else {
+        ("struct", "array")
+    };
+
+    if methods.is_empty() {
+        return vec![make_warning_tokens(
+            struct_name,
+            format!(
+                "The {} '{}' derives '{}' but does not contain any '{}' attribute, so '{}' does nothing.",
+                item_kind, struct_name, DERIVE_NAME, DECL_FN_NAME, DERIVE_NAME
+            ),
+        )];
+    }
+
+    methods
+        .iter()
+        .filter(|method| method.fields.is_empty() && !method.allow_empty)
+        .map(|method| {
+            make_warning_tokens(
+                &method.fn_name,
+                format!(
+                    "Method '{}' from {} '{}' returns an empty {}. Add the '{}' clause to '{}' to silence this warning.",
+                    method.fn_name,
+                    item_kind,
+                    struct_name,
+                    collection_kind,
+                    ALLOW_EMPTY_CLAUSE_NAME,
+                    DECL_FN_NAME
+                ),
+            )
+        })
+        .collect()
+}
+
+// There is no stable API for emitting warnings from proc macros yet, so we use a deprecated item
+// spanned on the offending tokens to make the compiler report the message as a regular warning.
+fn make_warning_tokens(spanned: &Ident, message: String) -> proc_macro2::TokenStream {
+    let warning = Ident::new("ArraygenWarning", spanned.span());
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct ArraygenWarning;
+            let _ = #warning;
+        };
+    }
+}
+
+fn make_impl_fns(methods: Vec<GenArray>, variants: &Option<Vec<Ident>>) -> Vec<TokenTree> {
     methods
         .into_iter()
-        .fold(Vec::<TokenTree>::new(), |mut acc, (name, method)| {
-            if method.fields.is_empty() {
-                eprintln!(
-                    //method.fn_name.span(), @TODO emit warning
-                    "warning (Arraygen): Method '{}' from struct '{}' returns an empty array.",
-                    name,
-                    struct_name
-                );
+        .fold(Vec::<TokenTree>::new(), |mut acc, method| {
+            if variants.is_none() {
+                acc.extend(make_len_tokens(&method));
+            }
+            if method.impl_trait.is_none() {
+                acc.extend(make_fn_tokens(&method, variants));
+            }
+            if let Some(names_fn) = &method.names_fn {
+                acc.extend(make_names_tokens(&method, names_fn));
+            }
+            if let Some(get_fn) = &method.get_fn {
+                acc.extend(make_get_tokens(&method, get_fn, variants));
+            }
+            if let Some(get_by_name_fn) = &method.get_by_name_fn {
+                acc.extend(make_get_by_name_tokens(&method, get_by_name_fn, variants));
             }
-            let tokens = make_method_tokens(&method);
-            acc.extend(tokens);
             acc
         })
 }
 
+fn make_fn_tokens(props: &GenArray, variants: &Option<Vec<Ident>>) -> proc_macro2::TokenStream {
+    match variants {
+        Some(variants) => make_enum_method_tokens(props, variants),
+        None => make_method_tokens(props),
+    }
+}
+
+// Methods with the 'impl_trait' clause are emitted in an impl of the given trait, instead of in the
+// inherent impl of the struct.
+fn make_impl_trait_impls(
+    methods: &[GenArray],
+    struct_name: &Ident,
+    generics: &Generics,
+    variants: &Option<Vec<Ident>>,
+) -> Vec<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    methods
+        .iter()
+        .filter_map(|method| {
+            method
+                .impl_trait
+                .as_ref()
+                .map(|trait_path| (method, trait_path))
+        })
+        .map(|(method, trait_path)| {
+            let fn_tokens = make_fn_tokens(method, variants);
+            quote! {
+                impl #impl_generics #trait_path for #struct_name #ty_generics
+                    #where_clause
+                {
+                    #fn_tokens
+                }
+            }
+        })
+        .collect()
+}
+
 fn make_method_tokens(props: &GenArray) -> proc_macro2::TokenStream {
-    let field_idents = &props.fields;
-    let count = field_idents.len();
-    let return_type = &props.fn_ty;
+    let count = make_count_tokens(props);
+    let item_type = make_item_type_tokens(props, &props.fn_ty);
     let vis = &props.vis;
     let fn_name = &props.fn_name;
+    let receiver = make_receiver_tokens(props);
+    let array = if props.fields.iter().any(|iae| iae.flatten) {
+        make_concatenation_tokens(props, &item_type, &count)
+    } else {
+        let field_idents = props.fields.iter().map(|iae| {
+            let member = &iae.member;
+            make_item_tokens(props, iae, quote! { self.#member })
+        });
+        quote! { [#(#field_idents),*] }
+    };
+
+    let return_type = make_return_type_tokens(props, quote! { [#item_type; #count] });
+    let body = make_body_tokens(props, array);
+
+    quote! {
+        #[inline(always)]
+        #vis fn #fn_name (#receiver) -> #return_type {
+            #body
+        }
+    }
+}
+
+// Flattened fields return arrays whose lengths are known at compile time, so they are concatenated
+// by writing each of them at its offset of the resulting array. The offsets are consecutive and the
+// lengths add up to the length of the array, so every element gets initialized exactly once. All
+// the elements are evaluated before writing any of them, so a failed conversion returns before
+// the array is partially initialized.
+fn make_concatenation_tokens(
+    props: &GenArray,
+    item_type: &proc_macro2::TokenStream,
+    count: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let fn_name = &props.fn_name;
+    let len_name = make_len_ident(fn_name);
+    let chunks = (0..props.fields.len())
+        .map(|i| format_ident!("__arraygen_chunk_{}", i))
+        .collect::<Vec<_>>();
+    let values = props.fields.iter().map(|iae| {
+        let member = &iae.member;
+        if iae.flatten {
+            quote! { self.#member.#fn_name() }
+        } else {
+            make_item_tokens(props, iae, quote! { self.#member })
+        }
+    });
+    let mut offset = quote! { 0 };
+    let mut writes = vec![];
+    for (iae, chunk) in props.fields.iter().zip(chunks.iter()) {
+        if iae.flatten {
+            let ty = erase_lifetimes(referenced_type(&iae.ty));
+            let len = quote! { <#ty>::#len_name };
+            writes.push(quote! {
+                ::std::ptr::write(__arraygen_start.add(#offset).cast::<[#item_type; #len]>(), #chunk);
+            });
+            offset = quote! { #offset + #len };
+        } else {
+            writes.push(quote! { ::std::ptr::write(__arraygen_start.add(#offset), #chunk); });
+            offset = quote! { #offset + 1 };
+        }
+    }
+
+    quote! {{
+        #(let #chunks = #values;)*
+        let mut __arraygen_array = ::std::mem::MaybeUninit::<[#item_type; #count]>::uninit();
+        let __arraygen_start = __arraygen_array.as_mut_ptr().cast::<#item_type>();
+        unsafe {
+            #(#writes)*
+            __arraygen_array.assume_init()
+        }
+    }}
+}
+
+// Methods with the 'tag' clause implement the traits from the 'arraygen-traits' crate, keyed by the
+// tag, so generic code can call them.
+fn make_trait_impls(
+    methods: &[GenArray],
+    struct_name: &Ident,
+    generics: &Generics,
+    is_enum: bool,
+) -> Vec<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    methods
+        .iter()
+        .filter_map(|method| method.tag.as_ref().map(|tag| (method, tag)))
+        .map(|(method, tag)| {
+            let fn_name = &method.fn_name;
+            let call = make_call_tokens(method);
+            let make_array_type = |item_type| {
+                if is_enum {
+                    quote! { ::std::vec::Vec<#item_type> }
+                } else {
+                    let count = make_count_tokens(method);
+                    quote! { [#item_type; #count] }
+                }
+            };
+
+            let field_array_impl = if method.by_value {
+                let item_type = make_item_type_tokens(method, &method.fn_ty);
+                let array_type = make_array_type(item_type.clone());
+                quote! {
+                    impl #impl_generics ::arraygen_traits::IntoFieldArray<#tag> for #struct_name #ty_generics
+                        #where_clause
+                    {
+                        type Item = #item_type;
+                        type Array = #array_type;
+
+                        #[inline(always)]
+                        fn into_field_array(self) -> Self::Array {
+                            #call
+                        }
+                    }
+                }
+            } else {
+                let lifetime = Lifetime::new("'__arraygen", proc_macro2::Span::call_site());
+                let item_type = make_item_type_tokens(
+                    method,
+                    &name_elided_lifetimes(&method.fn_ty, &lifetime),
+                );
+                let array_type = make_array_type(item_type.clone());
+                let (trait_name, trait_fn_name, receiver) = if method.is_mut {
+                    (
+                        quote! { FieldArrayMut },
+                        quote! { field_array_mut },
+                        quote! { &mut self },
+                    )
+                } else {
+                    (quote! { FieldArray }, quote! { field_array }, quote! { &self })
+                };
+                quote! {
+                    impl #impl_generics ::arraygen_traits::#trait_name<#tag> for #struct_name #ty_generics
+                        #where_clause
+                    {
+                        type Item<#lifetime> = #item_type where Self: #lifetime;
+                        type Array<#lifetime> = #array_type where Self: #lifetime;
+
+                        #[inline(always)]
+                        fn #trait_fn_name(#receiver) -> Self::Array<'_> {
+                            #call
+                        }
+                    }
+                }
+            };
+
+            if is_enum {
+                return field_array_impl;
+            }
+
+            let len_name = make_len_ident(fn_name);
+            quote! {
+                #field_array_impl
+
+                impl #impl_generics ::arraygen_traits::FieldArrayLen<#tag> for #struct_name #ty_generics
+                    #where_clause
+                {
+                    const LEN: usize = Self::#len_name;
+                }
+            }
+        })
+        .collect()
+}
+
+fn make_len_tokens(props: &GenArray) -> proc_macro2::TokenStream {
+    let count = make_count_tokens(props);
+    let vis = &props.vis;
+    let len_name = make_len_ident(&props.fn_name);
+
+    quote! {
+        #vis const #len_name: usize = #count;
+    }
+}
+
+// Flattened fields contribute the length of the method with the same name in their own type, which
+// is known at compile time through its associated constant.
+fn make_count_tokens(props: &GenArray) -> proc_macro2::TokenStream {
+    let count = props.fields.iter().filter(|iae| !iae.flatten).count();
+    let len_name = make_len_ident(&props.fn_name);
+    let flattened = props
+        .fields
+        .iter()
+        .filter(|iae| iae.flatten)
+        .map(|iae| erase_lifetimes(referenced_type(&iae.ty)))
+        .map(|ty| quote! { + <#ty>::#len_name });
+
+    quote! { #count #(#flattened)* }
+}
+
+fn referenced_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(reference) => referenced_type(&reference.elem),
+        _ => ty,
+    }
+}
+
+fn make_len_ident(fn_name: &Ident) -> Ident {
+    format_ident!(
+        "{}_LEN",
+        fn_name.unraw().to_string().to_uppercase(),
+        span = fn_name.span()
+    )
+}
+
+fn make_names_tokens(props: &GenArray, names_fn: &Ident) -> proc_macro2::TokenStream {
+    let count = props.fields.len();
+    let vis = &props.vis;
+    let names = props.fields.iter().map(|iae| member_to_string(&iae.member));
+
+    quote! {
+        #[inline(always)]
+        #vis const fn #names_fn () -> [&'static str; #count] {
+            [#(#names),*]
+        }
+    }
+}
+
+// The elements are accessed directly by their index, which is known at compile time for each
+// variant of enums too. Only the requested element is converted, so conversions of the other
+// elements can't make it fail.
+fn make_get_tokens(
+    props: &GenArray,
+    get_fn: &Ident,
+    variants: &Option<Vec<Ident>>,
+) -> proc_macro2::TokenStream {
+    let item_type = make_item_type_tokens(props, &props.fn_ty);
+    let vis = &props.vis;
+    let receiver = make_receiver_tokens(props);
+    let element = match variants {
+        Some(variants) => {
+            let arms = variants.iter().flat_map(|variant| {
+                let fields = props
+                    .fields
+                    .iter()
+                    .filter(|iae| iae.variant.as_ref() == Some(variant))
+                    .collect::<Vec<_>>();
+                if fields.iter().any(|iae| iae.flatten) {
+                    let (pattern, accesses) = make_variant_bindings(props, variant, &fields);
+                    let element = make_flattened_get_tokens(props, &fields, accesses, true);
+                    return vec![quote! { (#pattern, __arraygen_index) => #element }];
+                }
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, iae)| {
+                        let (pattern, accesses) = make_variant_bindings(props, variant, &[iae]);
+                        let item = make_item_tokens(props, iae, accesses[0].clone());
+                        quote! { (#pattern, #i) => ::std::option::Option::Some(#item) }
+                    })
+                    .collect()
+            });
+            quote! {
+                match (self, index) {
+                    #(#arms,)*
+                    #[allow(unreachable_patterns)]
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+        None => {
+            let fields = props.fields.iter().collect::<Vec<_>>();
+            let accesses = fields
+                .iter()
+                .map(|iae| {
+                    let member = &iae.member;
+                    quote! { self.#member }
+                })
+                .collect::<Vec<_>>();
+            if fields.iter().any(|iae| iae.flatten) {
+                let element = make_flattened_get_tokens(props, &fields, accesses, false);
+                quote! {{
+                    let __arraygen_index = index;
+                    #element
+                }}
+            } else {
+                let arms = fields
+                    .iter()
+                    .zip(accesses)
+                    .enumerate()
+                    .map(|(i, (iae, access))| {
+                        let item = make_item_tokens(props, iae, access);
+                        quote! { #i => ::std::option::Option::Some(#item) }
+                    });
+                quote! {
+                    match index {
+                        #(#arms,)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+            }
+        }
+    };
+
+    let return_type = make_return_type_tokens(props, quote! { ::std::option::Option<#item_type> });
+    let body = make_body_tokens(props, element);
+
+    quote! {
+        #[inline(always)]
+        #vis fn #get_fn (#receiver, index: usize) -> #return_type {
+            #body
+        }
+    }
+}
+
+// With flattened fields the indexes are known only after the length of the preceding flattened
+// arrays, so the index is reduced by the length of each field until it falls within one of them.
+// The lengths of flattened structs are known at compile time, while flattened enums have to build
+// their Vec to know it.
+fn make_flattened_get_tokens(
+    props: &GenArray,
+    fields: &[&InArrayElement],
+    accesses: Vec<proc_macro2::TokenStream>,
+    is_enum: bool,
+) -> proc_macro2::TokenStream {
+    let fn_name = &props.fn_name;
+    let len_name = make_len_ident(fn_name);
+    fields.iter().zip(accesses).rev().fold(
+        quote! { ::std::option::Option::None },
+        |rest, (iae, access)| {
+            if !iae.flatten {
+                let item = make_item_tokens(props, iae, access);
+                quote! {
+                    if __arraygen_index == 0 {
+                        ::std::option::Option::Some(#item)
+                    } else {
+                        let __arraygen_index = __arraygen_index - 1;
+                        #rest
+                    }
+                }
+            } else if is_enum {
+                quote! {{
+                    let mut __arraygen_chunk = ::std::iter::IntoIterator::into_iter(#access.#fn_name());
+                    let __arraygen_len = ::std::iter::ExactSizeIterator::len(&__arraygen_chunk);
+                    if __arraygen_index < __arraygen_len {
+                        ::std::iter::Iterator::nth(&mut __arraygen_chunk, __arraygen_index)
+                    } else {
+                        let __arraygen_index = __arraygen_index - __arraygen_len;
+                        #rest
+                    }
+                }}
+            } else {
+                let ty = erase_lifetimes(referenced_type(&iae.ty));
+                let len = quote! { <#ty>::#len_name };
+                quote! {
+                    if __arraygen_index < #len {
+                        ::std::iter::Iterator::nth(
+                            &mut ::std::iter::IntoIterator::into_iter(#access.#fn_name()),
+                            __arraygen_index,
+                        )
+                    } else {
+                        let __arraygen_index = __arraygen_index - #len;
+                        #rest
+                    }
+                }
+            }
+        },
+    )
+}
+
+fn make_call_tokens(props: &GenArray) -> proc_macro2::TokenStream {
+    let fn_name = &props.fn_name;
+    match &props.impl_trait {
+        Some(trait_path) => quote! { <Self as #trait_path>::#fn_name(self) },
+        None => quote! { self.#fn_name() },
+    }
+}
+
+fn make_enum_method_tokens(props: &GenArray, variants: &[Ident]) -> proc_macro2::TokenStream {
+    let item_type = make_item_type_tokens(props, &props.fn_ty);
+    let vis = &props.vis;
+    let fn_name = &props.fn_name;
+    let receiver = make_receiver_tokens(props);
+    let arms = variants.iter().map(|variant| {
+        let fields = props
+            .fields
+            .iter()
+            .filter(|iae| iae.variant.as_ref() == Some(variant))
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            return quote! { Self::#variant { .. } => ::std::vec::Vec::new() };
+        }
+        let count = fields.len();
+        let (pattern, accesses) = make_variant_bindings(props, variant, &fields);
+        if fields.iter().any(|iae| iae.flatten) {
+            let chunks = fields
+                .iter()
+                .zip(accesses)
+                .map(|(iae, access)| make_chunk_tokens(props, iae, access));
+            return quote! {
+                #pattern => {
+                    ::std::iter::Iterator::collect::<::std::vec::Vec<#item_type>>(
+                        ::std::iter::empty() #(.chain(#chunks))*
+                    )
+                }
+            };
+        }
+        let elements = fields
+            .iter()
+            .zip(accesses)
+            .map(|(iae, access)| make_item_tokens(props, iae, access));
+        quote! {
+            #pattern => {
+                let array: [#item_type; #count] = [#(#elements),*];
+                ::std::vec::Vec::from(array)
+            }
+        }
+    });
+
+    let body = make_variants_match_tokens(props, variants, arms);
+
+    let return_type = make_return_type_tokens(props, quote! { ::std::vec::Vec<#item_type> });
+    let body = make_body_tokens(props, body);
+
+    quote! {
+        #[inline(always)]
+        #vis fn #fn_name (#receiver) -> #return_type {
+            #body
+        }
+    }
+}
+
+// Binds the given fields of the variant, returning the pattern and the access to each field.
+fn make_variant_bindings(
+    props: &GenArray,
+    variant: &Ident,
+    fields: &[&InArrayElement],
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let bindings = (0..fields.len())
+        .map(|i| format_ident!("__arraygen_field_{}", i))
+        .collect::<Vec<_>>();
+    let members = fields.iter().map(|iae| &iae.member);
+    let pattern = quote! { Self::#variant { #(#members: #bindings,)* .. } };
+    let accesses = bindings
+        .iter()
+        .map(|binding| {
+            if props.by_value {
+                quote! { #binding }
+            } else {
+                quote! { (*#binding) }
+            }
+        })
+        .collect();
+    (pattern, accesses)
+}
+
+fn make_variants_match_tokens(
+    props: &GenArray,
+    variants: &[Ident],
+    arms: impl Iterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    if variants.is_empty() && !props.by_value {
+        quote! { match *self {} }
+    } else {
+        quote! { match self { #(#arms),* } }
+    }
+}
+
+// Elements are matched by the name of their field. Flattened fields have no name of their own, so
+// they can't be used along with this method.
+fn make_get_by_name_tokens(
+    props: &GenArray,
+    get_by_name_fn: &Ident,
+    variants: &Option<Vec<Ident>>,
+) -> proc_macro2::TokenStream {
+    let item_type = make_item_type_tokens(props, &props.fn_ty);
+    let vis = &props.vis;
+    let receiver = make_receiver_tokens(props);
+    let make_name_match = |fields: &[&InArrayElement], accesses: Vec<proc_macro2::TokenStream>| {
+        let arms = fields.iter().zip(accesses).map(|(iae, access)| {
+            let name = member_to_string(&iae.member);
+            let item = make_item_tokens(props, iae, access);
+            quote! { #name => ::std::option::Option::Some(#item) }
+        });
+        quote! {
+            match name {
+                #(#arms,)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    };
+    let element = match variants {
+        Some(variants) => {
+            let arms = variants.iter().map(|variant| {
+                let fields = props
+                    .fields
+                    .iter()
+                    .filter(|iae| iae.variant.as_ref() == Some(variant))
+                    .collect::<Vec<_>>();
+                if fields.is_empty() {
+                    return quote! { Self::#variant { .. } => ::std::option::Option::None };
+                }
+                let (pattern, accesses) = make_variant_bindings(props, variant, &fields);
+                let name_match = make_name_match(&fields, accesses);
+                quote! { #pattern => #name_match }
+            });
+            make_variants_match_tokens(props, variants, arms)
+        }
+        None => {
+            let fields = props.fields.iter().collect::<Vec<_>>();
+            let accesses = fields
+                .iter()
+                .map(|iae| {
+                    let member = &iae.member;
+                    quote! { self.#member }
+                })
+                .collect();
+            make_name_match(&fields, accesses)
+        }
+    };
+
+    let return_type = make_return_type_tokens(props, quote! { ::std::option::Option<#item_type> });
+    let body = make_body_tokens(props, element);
+
+    quote! {
+        #[inline(always)]
+        #vis fn #get_by_name_fn (#receiver, name: &str) -> #return_type {
+            #body
+        }
+    }
+}
+
+fn make_return_type_tokens(
+    props: &GenArray,
+    return_type: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if props.is_fallible() {
+        quote! {
+            ::std::result::Result<
+                #return_type,
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+            >
+        }
+    } else {
+        return_type
+    }
+}
+
+fn make_body_tokens(props: &GenArray, body: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if props.is_fallible() {
+        quote! { ::std::result::Result::Ok(#body) }
+    } else {
+        body
+    }
+}
+
+fn make_receiver_tokens(props: &GenArray) -> proc_macro2::TokenStream {
+    if props.by_value {
+        quote! {self}
+    } else if props.is_mut {
+        quote! {&mut self}
+    } else {
+        quote! {&self}
+    }
+}
+
+fn make_item_type_tokens(props: &GenArray, return_type: &Type) -> proc_macro2::TokenStream {
+    if props.with_names {
+        quote! { (&'static str, #return_type) }
+    } else {
+        quote! { #return_type }
+    }
+}
+
+// Flattened fields of enum variants are spliced by iterating the result of their own method with
+// the same name, since the resulting Vec has no length known at compile time.
+fn make_chunk_tokens(
+    props: &GenArray,
+    iae: &InArrayElement,
+    access: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if iae.flatten {
+        let fn_name = &props.fn_name;
+        quote! { ::std::iter::IntoIterator::into_iter(#access.#fn_name()) }
+    } else {
+        let item = make_item_tokens(props, iae, access);
+        quote! { ::std::iter::once(#item) }
+    }
+}
+
+fn make_item_tokens(
+    props: &GenArray,
+    iae: &InArrayElement,
+    access: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let element = make_element_tokens(props, iae, access);
+    if props.with_names {
+        let name = member_to_string(&iae.member);
+        quote! { (#name, #element) }
+    } else {
+        element
+    }
+}
+
+fn make_element_tokens(
+    props: &GenArray,
+    iae: &InArrayElement,
+    access: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let return_type = &props.fn_ty;
     let refa = if props.is_ref {
         if props.is_mut {
             quote! {&mut}
@@ -74,33 +776,79 @@ fn make_method_tokens(props: &GenArray) -> proc_macro2::TokenStream {
     } else {
         quote! {}
     };
-    let muta = if props.is_mut {
-        quote! {mut}
+    match iae.cast {
+        Some(CastKind::SafeCast) => quote! { #refa #access as #return_type },
+        Some(CastKind::UnsafeTransmute) => {
+            let source_ty = &iae.ty;
+            let refb = match source_ty {
+                Type::Reference(_) if props.is_ref => quote! {},
+                _ => quote! { #refa },
+            };
+            quote! { unsafe { std::mem::transmute::<#refb #source_ty, #return_type>(#refa #access) } }
+        }
+        Some(CastKind::Into) => {
+            let value = make_owned_value_tokens(props, access);
+            quote! { ::std::convert::Into::<#return_type>::into(#value) }
+        }
+        Some(CastKind::TryInto) => {
+            let value = make_owned_value_tokens(props, access);
+            quote! { ::std::convert::TryInto::<#return_type>::try_into(#value)? }
+        }
+        Some(CastKind::With(ref function)) => {
+            let argument = if props.by_value {
+                access
+            } else if props.is_mut {
+                quote! { &mut #access }
+            } else {
+                quote! { &#access }
+            };
+            quote! { (#function)(#argument) }
+        }
+        None => quote! { #refa #access },
+    }
+}
+
+fn make_owned_value_tokens(
+    props: &GenArray,
+    access: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if props.by_value {
+        access
     } else {
-        quote! {}
-    };
-    let field_idents = field_idents
-        .iter()
-        .map(|iae| {
-            let ident = iae.ident.clone();
-            match iae.cast {
-                Some(CastKind::SafeCast) => quote! { #refa self.#ident as #return_type },
-                Some(CastKind::UnsafeTransmute) => {
-                    let source_ty = &iae.ty;
-                    let refb = match source_ty {
-                        Type::Reference(_) if props.is_ref => quote! {},
-                        _ => quote! { #refa }
-                    };
-                    quote ! { unsafe { std::mem::transmute::<#refb #source_ty, #return_type>(#refa self.#ident) } }
-                },
-                None => quote! { #refa self.#ident }
-            }
-        });
+        quote! { ::std::clone::Clone::clone(&#access) }
+    }
+}
 
-    quote! {
-        #[inline(always)]
-        #vis fn #fn_name (& #muta self) -> [#return_type; #count] {
-            [#(#field_idents),*]
-        }
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn make_impl_fns___with_many_gen_arrays___emits_methods_in_declaration_order() {
+        let names = [
+            "zeta", "alpha", "omega", "beta", "kappa", "delta", "gamma", "epsilon",
+        ];
+        let declarations = names
+            .iter()
+            .map(|name| format!("#[gen_array(fn {}: i32, implicit_select_all: i32)]", name))
+            .collect::<String>();
+        let arraygen: DeriveArraygen =
+            syn::parse_str(&format!("{} struct Sut {{ a: i32 }}", declarations)).unwrap();
+
+        let declared = arraygen
+            .gen_arrays
+            .iter()
+            .map(|ga| ga.fn_name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(declared, names);
+
+        let impl_fns = make_impl_fns(arraygen.gen_arrays, &None);
+        let expansion = quote! { #(#impl_fns)* }.to_string();
+        let positions = names
+            .iter()
+            .map(|name| expansion.find(&format!("fn {} (", name)).unwrap())
+            .collect::<Vec<_>>();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
     }
 }
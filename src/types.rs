@@ -1,6 +1,58 @@
-use proc_macro2::{token_stream::IntoIter, TokenTree, TokenTree::*};
-use quote::quote;
-use syn::Type;
+use proc_macro2::{Group as TokenGroup, TokenStream, TokenTree, TokenTree::*};
+use quote::{quote, ToTokens};
+use syn::parse::{ParseStream, Result};
+use syn::{
+    AngleBracketedGenericArguments, GenericArgument, Lifetime, Path, PathArguments, ReturnType,
+    Type, TypeParamBound,
+};
+
+// 'dyn _' is not valid Rust syntax, so trait wildcards are replaced by this identifier before
+// parsing the type patterns.
+const TRAIT_WILDCARD: &str = "__arraygen_trait_wildcard";
+
+const PRELUDE_CRATES: &[&str] = &["std", "core", "alloc"];
+
+// Keywords that may be followed by a path in a type, like in '&mut ::std::string::String'.
+const PATH_PREFIX_KEYWORDS: &[&str] = &["as", "const", "dyn", "impl", "mut"];
+
+// Items that are in scope through the prelude, so writing their full path is equivalent to just
+// writing their name.
+const PRELUDE_PATHS: &[(&str, &str)] = &[
+    ("option", "Option"),
+    ("result", "Result"),
+    ("string", "String"),
+    ("string", "ToString"),
+    ("vec", "Vec"),
+    ("boxed", "Box"),
+    ("borrow", "ToOwned"),
+    ("clone", "Clone"),
+    ("cmp", "PartialEq"),
+    ("cmp", "Eq"),
+    ("cmp", "PartialOrd"),
+    ("cmp", "Ord"),
+    ("convert", "AsRef"),
+    ("convert", "AsMut"),
+    ("convert", "From"),
+    ("convert", "Into"),
+    ("convert", "TryFrom"),
+    ("convert", "TryInto"),
+    ("default", "Default"),
+    ("iter", "Iterator"),
+    ("iter", "IntoIterator"),
+    ("iter", "DoubleEndedIterator"),
+    ("iter", "ExactSizeIterator"),
+    ("iter", "Extend"),
+    ("iter", "FromIterator"),
+    ("marker", "Copy"),
+    ("marker", "Send"),
+    ("marker", "Sized"),
+    ("marker", "Sync"),
+    ("marker", "Unpin"),
+    ("ops", "Drop"),
+    ("ops", "Fn"),
+    ("ops", "FnMut"),
+    ("ops", "FnOnce"),
+];
 
 pub fn are_matching_types(left_ty: &Type, right_ty: &Type) -> bool {
     compare_types(left_ty, right_ty, true)
@@ -10,112 +62,441 @@ pub fn ty_inferred_by(field_ty: &Type, implicit_ty: &Type) -> bool {
     compare_types(field_ty, implicit_ty, false)
 }
 
+// Whether the type is exactly one of the given type parameters, like 'T' but not 'Option<T>'.
+pub fn is_type_param(ty: &Type, type_params: &[syn::Ident]) -> bool {
+    match ty {
+        Type::Paren(paren) => is_type_param(&paren.elem, type_params),
+        Type::Group(group) => is_type_param(&group.elem, type_params),
+        Type::Path(path) if path.qself.is_none() => type_params
+            .iter()
+            .any(|type_param| path.path.is_ident(type_param)),
+        _ => false,
+    }
+}
+
+// Tells whether any of the given generic parameters appears in the type, like 'T' in 'Vec<T::Item>'.
+pub fn mentions_generic_params(ty: &Type, params: &[syn::Ident]) -> bool {
+    mentions_generic_param_tokens(ty.to_token_stream(), params)
+}
+
+fn mentions_generic_param_tokens(tokens: TokenStream, params: &[syn::Ident]) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        Ident(ident) => {
+            let is_lifetime = matches!(i.checked_sub(1).map(|j| &tokens[j]), Some(Punct(p)) if p.as_char() == '\'');
+            let continues_path = i >= 2 && is_path_separator(&tokens[i - 2..i]);
+            !is_lifetime && !continues_path && params.iter().any(|param| ident == param)
+        }
+        Group(group) => mentions_generic_param_tokens(group.stream(), params),
+        _ => false,
+    })
+}
+
 fn compare_types(left_ty: &Type, right_ty: &Type, wildcards_on_left: bool) -> bool {
-    if *left_ty == *right_ty {
-        return true;
+    let matcher = TypeMatcher { wildcards_on_left };
+    matcher.types(
+        &normalize_prelude_paths(left_ty),
+        &normalize_prelude_paths(right_ty),
+    )
+}
+
+// Compares types structurally. Wildcards on the right side ('_', '\'_', 'dyn _' and references
+// without lifetime) match anything in the same position on the left side, and the other way
+// around when 'wildcards_on_left' is set.
+struct TypeMatcher {
+    wildcards_on_left: bool,
+}
+
+impl TypeMatcher {
+    fn types(&self, left: &Type, right: &Type) -> bool {
+        match (left, right) {
+            (_, Type::Infer(_)) => true,
+            (Type::Infer(_), _) => self.wildcards_on_left,
+            (Type::Paren(left), _) => self.types(&left.elem, right),
+            (_, Type::Paren(right)) => self.types(left, &right.elem),
+            (Type::Group(left), _) => self.types(&left.elem, right),
+            (_, Type::Group(right)) => self.types(left, &right.elem),
+            (Type::Path(left), Type::Path(right)) => {
+                self.optional(left.qself.as_ref(), right.qself.as_ref(), |left, right| {
+                    left.position == right.position && self.types(&left.ty, &right.ty)
+                }) && self.paths(&left.path, &right.path)
+            }
+            (Type::Reference(left), Type::Reference(right)) => {
+                left.mutability.is_some() == right.mutability.is_some()
+                    && self.optional_lifetimes(left.lifetime.as_ref(), right.lifetime.as_ref())
+                    && self.types(&left.elem, &right.elem)
+            }
+            (Type::Ptr(left), Type::Ptr(right)) => {
+                left.mutability.is_some() == right.mutability.is_some()
+                    && self.types(&left.elem, &right.elem)
+            }
+            (Type::Slice(left), Type::Slice(right)) => self.types(&left.elem, &right.elem),
+            (Type::Array(left), Type::Array(right)) => {
+                self.types(&left.elem, &right.elem) && same_tokens(&left.len, &right.len)
+            }
+            (Type::Tuple(left), Type::Tuple(right)) => {
+                self.all(left.elems.iter(), right.elems.iter(), |l, r| {
+                    self.types(l, r)
+                })
+            }
+            (Type::TraitObject(left), Type::TraitObject(right)) => {
+                self.bounds(left.bounds.iter(), right.bounds.iter())
+            }
+            (Type::ImplTrait(left), Type::ImplTrait(right)) => {
+                self.bounds(left.bounds.iter(), right.bounds.iter())
+            }
+            (Type::BareFn(left), Type::BareFn(right)) => {
+                same_tokens(&left.lifetimes, &right.lifetimes)
+                    && same_tokens(&left.unsafety, &right.unsafety)
+                    && same_tokens(&left.abi, &right.abi)
+                    && left.variadic.is_some() == right.variadic.is_some()
+                    && self.all(left.inputs.iter(), right.inputs.iter(), |l, r| {
+                        self.types(&l.ty, &r.ty)
+                    })
+                    && self.return_types(&left.output, &right.output)
+            }
+            (Type::Never(_), Type::Never(_)) => true,
+            (Type::Macro(left), Type::Macro(right)) => same_tokens(left, right),
+            (Type::Verbatim(left), Type::Verbatim(right)) => left.to_string() == right.to_string(),
+            _ => false,
+        }
     }
-    if let Type::Infer(_) = right_ty {
-        return true;
+
+    fn paths(&self, left: &Path, right: &Path) -> bool {
+        left.leading_colon.is_some() == right.leading_colon.is_some()
+            && self.all(left.segments.iter(), right.segments.iter(), |l, r| {
+                l.ident == r.ident && self.path_arguments(&l.arguments, &r.arguments)
+            })
     }
-    if let (true, Type::Infer(_)) = (wildcards_on_left, left_ty) {
-        return true;
+
+    fn path_arguments(&self, left: &PathArguments, right: &PathArguments) -> bool {
+        match (left, right) {
+            (PathArguments::None, PathArguments::None) => true,
+            (PathArguments::AngleBracketed(left), PathArguments::AngleBracketed(right)) => {
+                // Like elided lifetimes in references, omitting every lifetime argument in a
+                // pattern matches any lifetimes.
+                let has_lifetimes = |args: &AngleBracketedGenericArguments| {
+                    args.args
+                        .iter()
+                        .any(|arg| matches!(arg, GenericArgument::Lifetime(_)))
+                };
+                let skip_left_lifetimes = !has_lifetimes(right);
+                let skip_right_lifetimes = self.wildcards_on_left && !has_lifetimes(left);
+                let left_args = left.args.iter().filter(|arg| {
+                    !(skip_left_lifetimes && matches!(arg, GenericArgument::Lifetime(_)))
+                });
+                let right_args = right.args.iter().filter(|arg| {
+                    !(skip_right_lifetimes && matches!(arg, GenericArgument::Lifetime(_)))
+                });
+                self.all(left_args, right_args, |l, r| self.generic_arguments(l, r))
+            }
+            (PathArguments::Parenthesized(left), PathArguments::Parenthesized(right)) => {
+                self.all(left.inputs.iter(), right.inputs.iter(), |l, r| {
+                    self.types(l, r)
+                }) && self.return_types(&left.output, &right.output)
+            }
+            _ => false,
+        }
     }
 
-    let mut right_tokens = quote! { #right_ty }.into_iter();
-    let mut left_tokens = quote! { #left_ty }.into_iter();
+    fn generic_arguments(&self, left: &GenericArgument, right: &GenericArgument) -> bool {
+        match (left, right) {
+            (GenericArgument::Type(left), GenericArgument::Type(right)) => self.types(left, right),
+            (GenericArgument::Lifetime(left), GenericArgument::Lifetime(right)) => {
+                self.lifetimes(left, right)
+            }
+            (GenericArgument::Binding(left), GenericArgument::Binding(right)) => {
+                left.ident == right.ident && self.types(&left.ty, &right.ty)
+            }
+            (GenericArgument::Constraint(left), GenericArgument::Constraint(right)) => {
+                left.ident == right.ident && self.bounds(left.bounds.iter(), right.bounds.iter())
+            }
+            (GenericArgument::Const(left), GenericArgument::Const(right)) => {
+                same_tokens(left, right)
+            }
+            _ => false,
+        }
+    }
 
-    let mut last_group = 'Z';
+    fn bounds<'a>(
+        &self,
+        left: impl Iterator<Item = &'a TypeParamBound>,
+        right: impl Iterator<Item = &'a TypeParamBound>,
+    ) -> bool {
+        let left = left.collect::<Vec<_>>();
+        let right = right.collect::<Vec<_>>();
+        if is_trait_wildcard(&right) || (self.wildcards_on_left && is_trait_wildcard(&left)) {
+            return true;
+        }
+        self.all(left.into_iter(), right.into_iter(), |l, r| match (l, r) {
+            (TypeParamBound::Trait(left), TypeParamBound::Trait(right)) => {
+                same_tokens(&left.modifier, &right.modifier)
+                    && same_tokens(&left.lifetimes, &right.lifetimes)
+                    && self.paths(&left.path, &right.path)
+            }
+            (TypeParamBound::Lifetime(left), TypeParamBound::Lifetime(right)) => {
+                self.lifetimes(left, right)
+            }
+            _ => false,
+        })
+    }
 
-    loop {
-        let (left_t, right_t) = match (left_tokens.next(), right_tokens.next()) {
-            (Some(lt), Some(rt)) => (lt, rt),
-            (Some(_), None) | (None, Some(_)) => return false,
-            (None, None) => return true,
-        };
+    fn return_types(&self, left: &ReturnType, right: &ReturnType) -> bool {
+        match (left, right) {
+            (ReturnType::Default, ReturnType::Default) => true,
+            (ReturnType::Type(_, left), ReturnType::Type(_, right)) => self.types(left, right),
+            _ => false,
+        }
+    }
 
-        match right_t {
-            Punct(ref p) if p.as_char() == '(' || p.as_char() == '<' || p.as_char() == '[' => {
-                last_group = p.as_char()
-            }
-            _ => {}
+    fn lifetimes(&self, left: &Lifetime, right: &Lifetime) -> bool {
+        self.optional_lifetimes(Some(left), Some(right))
+    }
+
+    fn optional_lifetimes(&self, left: Option<&Lifetime>, right: Option<&Lifetime>) -> bool {
+        let is_wildcard = |lifetime: Option<&Lifetime>| match lifetime {
+            Some(lifetime) => lifetime.ident == "_",
+            None => true,
+        };
+        match (left, right) {
+            _ if is_wildcard(right) => true,
+            _ if self.wildcards_on_left && is_wildcard(left) => true,
+            (Some(left), Some(right)) => left.ident == right.ident,
+            _ => false,
         }
+    }
 
-        match (&left_t, &right_t) {
-            (Punct(p1), Punct(p2)) if p1.as_char() == p2.as_char() => continue,
-            (Ident(i1), Ident(i2)) if i1 == i2 => continue,
-            (Literal(l1), Literal(l2)) if l1.to_string() == l2.to_string() => continue,
-            (Group(g1), Group(g2)) if g1.to_string() == g2.to_string() => continue,
-            _ => {}
+    fn optional<T>(
+        &self,
+        left: Option<&T>,
+        right: Option<&T>,
+        compare: impl Fn(&T, &T) -> bool,
+    ) -> bool {
+        match (left, right) {
+            (Some(left), Some(right)) => compare(left, right),
+            (None, None) => true,
+            _ => false,
         }
+    }
 
-        let mut termination = AdvanceTermination {
-            wildcard_ended: false,
-            other_ended: false,
-        };
+    fn all<T>(
+        &self,
+        left: impl Iterator<Item = T>,
+        right: impl Iterator<Item = T>,
+        compare: impl Fn(T, T) -> bool,
+    ) -> bool {
+        let left = left.collect::<Vec<_>>();
+        let right = right.collect::<Vec<_>>();
+        left.len() == right.len() && left.into_iter().zip(right).all(|(l, r)| compare(l, r))
+    }
+}
+
+fn is_trait_wildcard(bounds: &[&TypeParamBound]) -> bool {
+    matches!(bounds, [TypeParamBound::Trait(bound)] if bound.path.is_ident(TRAIT_WILDCARD))
+}
+
+fn same_tokens<T: ToTokens>(left: &T, right: &T) -> bool {
+    left.to_token_stream().to_string() == right.to_token_stream().to_string()
+}
 
-        if advance_if_wildcard(
-            &right_t,
-            &mut right_tokens,
-            &mut left_tokens,
-            last_group,
-            &mut termination,
-        ) || (wildcards_on_left
-            && advance_if_wildcard(
-                &left_t,
-                &mut left_tokens,
-                &mut right_tokens,
-                last_group,
-                &mut termination,
-            ))
-        {
-            match (termination.wildcard_ended, termination.other_ended) {
-                (true, true) => return true,
-                (true, false) | (false, true) => return false,
-                (false, false) => continue,
+// Parses a type that may contain wildcards, including 'dyn _' for any trait object.
+pub fn parse_type_pattern(input: ParseStream) -> Result<Type> {
+    let tokens = input.step(|cursor| {
+        let mut rest = *cursor;
+        let mut tokens = vec![];
+        let mut depth = 0;
+        while let Some((token, next)) = rest.token_tree() {
+            match &token {
+                Punct(p) if p.as_char() == ',' && depth == 0 => break,
+                Punct(p) if p.as_char() == '<' => depth += 1,
+                Punct(p) if p.as_char() == '>' && !is_arrow_end(&tokens) => depth -= 1,
+                _ => {}
             }
+            tokens.push(token);
+            rest = next;
         }
-        return false;
+        Ok((tokens, rest))
+    })?;
+    syn::parse2(replace_trait_wildcards(tokens.into_iter().collect()))
+}
+
+fn is_arrow_end(previous_tokens: &[TokenTree]) -> bool {
+    matches!(previous_tokens.last(), Some(Punct(p)) if p.as_char() == '-')
+}
+
+fn replace_trait_wildcards(tokens: TokenStream) -> TokenStream {
+    let mut replaced: Vec<TokenTree> = vec![];
+    for token in tokens {
+        let after_dyn = matches!(replaced.last(), Some(Ident(ident)) if ident == "dyn");
+        replaced.push(match token {
+            Ident(ident) if after_dyn && ident == "_" => {
+                Ident(proc_macro2::Ident::new(TRAIT_WILDCARD, ident.span()))
+            }
+            Group(group) => {
+                let mut new_group =
+                    TokenGroup::new(group.delimiter(), replace_trait_wildcards(group.stream()));
+                new_group.set_span(group.span());
+                Group(new_group)
+            }
+            token => token,
+        });
     }
+    replaced.into_iter().collect()
 }
 
-struct AdvanceTermination {
-    pub wildcard_ended: bool,
-    pub other_ended: bool,
+// Replaces every lifetime with 'static, since lifetimes can't be used in constant expressions.
+pub fn erase_lifetimes(ty: &Type) -> Type {
+    syn::parse2(erase_lifetime_tokens(ty.to_token_stream())).unwrap_or_else(|_| ty.clone())
 }
 
-fn advance_if_wildcard(
-    wildcard_token: &TokenTree,
-    wildcard_iter: &mut IntoIter,
-    other_iter: &mut IntoIter,
-    last_group: char,
-    termination: &mut AdvanceTermination,
-) -> bool {
-    if !matches!(wildcard_token, Ident(ref p) if p == "_") {
-        return false;
+fn erase_lifetime_tokens(tokens: TokenStream) -> TokenStream {
+    let mut erased: Vec<TokenTree> = vec![];
+    for token in tokens {
+        let after_quote = matches!(erased.last(), Some(Punct(p)) if p.as_char() == '\'');
+        erased.push(match token {
+            Ident(ident) if after_quote => Ident(proc_macro2::Ident::new("static", ident.span())),
+            Group(group) => {
+                let mut new_group =
+                    TokenGroup::new(group.delimiter(), erase_lifetime_tokens(group.stream()));
+                new_group.set_span(group.span());
+                Group(new_group)
+            }
+            token => token,
+        });
     }
+    erased.into_iter().collect()
+}
+
+// Gives a name to the elided lifetimes of references and to '_, so the type can be used where
+// lifetimes can't be elided, like in associated types.
+pub fn name_elided_lifetimes(ty: &Type, lifetime: &Lifetime) -> Type {
+    syn::parse2(name_elided_lifetime_tokens(ty.to_token_stream(), lifetime))
+        .unwrap_or_else(|_| ty.clone())
+}
 
-    match wildcard_iter.next() {
-        Some(_) => {}
-        None => {
-            termination.wildcard_ended = true;
+fn name_elided_lifetime_tokens(tokens: TokenStream, lifetime: &Lifetime) -> TokenStream {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut named = TokenStream::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let is_quote =
+            |token: Option<&TokenTree>| matches!(token, Some(Punct(p)) if p.as_char() == '\'');
+        match token {
+            Punct(p) if p.as_char() == '&' => {
+                named.extend(Some(token.clone()));
+                if !is_quote(tokens.get(i + 1)) {
+                    lifetime.to_tokens(&mut named);
+                }
+            }
+            Ident(ident) if ident == "_" && i > 0 && is_quote(tokens.get(i - 1)) => {
+                named.extend(Some(Ident(lifetime.ident.clone())));
+            }
+            Group(group) => {
+                let mut new_group = TokenGroup::new(
+                    group.delimiter(),
+                    name_elided_lifetime_tokens(group.stream(), lifetime),
+                );
+                new_group.set_span(group.span());
+                named.extend(Some(Group(new_group)));
+            }
+            token => named.extend(Some(token.clone())),
         }
     }
+    named
+}
 
-    for other_token in other_iter {
-        match other_token {
-            Punct(ref p)
-                if (p.as_char() == ')' && last_group == '(')
-                    || (p.as_char() == '>' && last_group == '<')
-                    || (p.as_char() == ']' && last_group == '[')
-                    || p.as_char() == ',' =>
-            {
-                return true;
+pub fn type_pattern_to_string(ty: &Type) -> String {
+    quote! { #ty }.to_string().replace(TRAIT_WILDCARD, "_")
+}
+
+fn normalize_prelude_paths(ty: &Type) -> Type {
+    let tokens = normalize_prelude_tokens(quote! { #ty });
+    syn::parse2(tokens).unwrap_or_else(|_| ty.clone())
+}
+
+fn normalize_prelude_tokens(tokens: TokenStream) -> TokenStream {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut normalized = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let continues_path = (i >= 2 && is_path_separator(&tokens[i - 2..i]))
+            || (is_path_separator(&tokens[i..]) && ends_path_segment(&tokens[..i]));
+        match prelude_path_len(&tokens[i..]) {
+            Some(len) if !continues_path => {
+                normalized.push(tokens[i + len - 1].clone());
+                i += len;
+            }
+            _ => {
+                normalized.push(match &tokens[i] {
+                    Group(group) => {
+                        let mut new_group = TokenGroup::new(
+                            group.delimiter(),
+                            normalize_prelude_tokens(group.stream()),
+                        );
+                        new_group.set_span(group.span());
+                        Group(new_group)
+                    }
+                    token => token.clone(),
+                });
+                i += 1;
             }
-            _ => {}
         }
     }
-    termination.other_ended = true;
-    true
+    normalized.into_iter().collect()
+}
+
+// Tells whether the given tokens end with a path segment, like 'my' in 'my::std::string::String' or
+// '>' in 'Vec<u8>::std', in which case a following '::' can't start a leading-colon path. Lifetimes,
+// keywords and arrows like in '&'a ::std', '&mut ::std' or 'fn() -> ::std' don't end path segments.
+fn ends_path_segment(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [.., Punct(quote), Ident(_)] if quote.as_char() == '\'' => false,
+        [.., Ident(ident)] => !PATH_PREFIX_KEYWORDS.iter().any(|keyword| ident == keyword),
+        [.., Punct(dash), Punct(_)] if dash.as_char() == '-' => false,
+        [.., Punct(p)] => p.as_char() == '>',
+        _ => false,
+    }
+}
+
+// Returns the number of tokens of a path like '::std::string::String' or 'core::primitive::u8'
+// starting at the beginning of the given tokens, if it points to an item of the prelude.
+fn prelude_path_len(tokens: &[TokenTree]) -> Option<usize> {
+    let start = if is_path_separator(tokens) { 2 } else { 0 };
+    let ident_at = |pos: usize| match tokens.get(pos) {
+        Some(Ident(ident)) => Some(ident.to_string()),
+        _ => None,
+    };
+    let separator_at = |pos: usize| tokens.len() >= pos + 2 && is_path_separator(&tokens[pos..]);
+
+    let krate = ident_at(start)?;
+    if !PRELUDE_CRATES.contains(&krate.as_str()) || !separator_at(start + 1) {
+        return None;
+    }
+    let module = ident_at(start + 3)?;
+    if !separator_at(start + 4) {
+        return None;
+    }
+    let name = ident_at(start + 6)?;
+    if separator_at(start + 7) {
+        return None;
+    }
+
+    let is_prelude = module == "primitive"
+        || PRELUDE_PATHS.iter().any(|(prelude_module, prelude_name)| {
+            module == *prelude_module && name == *prelude_name
+        });
+    if is_prelude {
+        Some(start + 7)
+    } else {
+        None
+    }
+}
+
+fn is_path_separator(tokens: &[TokenTree]) -> bool {
+    matches!(
+        tokens,
+        [Punct(first), Punct(second), ..] if first.as_char() == ':' && second.as_char() == ':'
+            && first.spacing() == proc_macro2::Spacing::Joint
+    )
 }
 
 #[allow(non_snake_case)]
@@ -123,8 +504,11 @@ fn advance_if_wildcard(
 mod test {
     use super::*;
 
+    // Besides a test for each case, the table is collected in 'TY_INFERRED_BY_CASES' so the property
+    // tests can check every case within random contexts.
     macro_rules! ty_inferred_by_tests {
         ($($name:ident: $str1:expr, $str2:expr, $expected:expr)*) => {
+        const TY_INFERRED_BY_CASES: &[(&str, &str, bool)] = &[$(($str1, $str2, $expected)),*];
         $(
             #[test]
             fn $name() {
@@ -153,6 +537,78 @@ mod test {
         ty_inferred_by___compared_to_wildcard_6___returns_true: "Result<i32, Option<i32>>", "Result<_, Option<i32>>", true
         ty_inferred_by___with_matching_wildcards_in_both_sides_1___returns_true: "Result<_, i32>", "Result<_, i32>", true
         ty_inferred_by___with_matching_wildcards_in_both_sides_2___returns_true: "Result<i32, _>", "Result<i32, _>", true
+        ty_inferred_by___with_prelude_path_on_field___returns_true: "std::string::String", "String", true
+        ty_inferred_by___with_prelude_path_on_pattern___returns_true: "String", "::alloc::string::String", true
+        ty_inferred_by___with_nested_prelude_paths___returns_true: "std::option::Option<(core::primitive::u8, std::vec::Vec<u8>)>", "Option<(u8, Vec<u8>)>", true
+        ty_inferred_by___with_prelude_trait_paths___returns_true: "Box<dyn Fn() + core::marker::Send>", "std::boxed::Box<dyn std::ops::Fn() + Send>", true
+        ty_inferred_by___with_non_prelude_path___returns_false: "std::collections::HashMap<i32, i32>", "HashMap<i32, i32>", false
+        ty_inferred_by___with_longer_path_ending_like_prelude_path___returns_false: "my::std::string::String", "String", false
+        ty_inferred_by___with_user_std_module_next_to_prelude_path___returns_true: "(my::std::string::String, std::string::String)", "(my::std::string::String, String)", true
+        ty_inferred_by___with_user_std_module_inside_generic___returns_true: "Vec<(my::std::option::Option<u8>, std::option::Option<u8>)>", "Vec<(my::std::option::Option<u8>, Option<u8>)>", true
+        ty_inferred_by___with_user_std_module_next_to_prelude_name___returns_false: "(my::std::string::String, String)", "(String, String)", false
+        ty_inferred_by___with_leading_colon_prelude_path_after_keyword___returns_true: "(&mut ::std::string::String, *const ::core::primitive::u8)", "(&mut String, *const u8)", true
+        ty_inferred_by___with_leading_colon_prelude_path_after_lifetime___returns_true: "&'a ::alloc::string::String", "&'a String", true
+        ty_inferred_by___with_leading_colon_prelude_path_after_arrow___returns_true: "fn() -> ::std::string::String", "fn() -> String", true
+        ty_inferred_by___with_different_prelude_types___returns_false: "std::string::String", "Vec<u8>", false
+        ty_inferred_by___with_reference_wildcard___returns_true: "&'a str", "&_", true
+        ty_inferred_by___with_reference_wildcard_on_mutable_reference___returns_false: "&'a mut str", "&_", false
+        ty_inferred_by___with_mutable_reference_wildcard___returns_true: "&mut Vec<u8>", "&mut _", true
+        ty_inferred_by___with_mutable_reference_wildcard_on_shared_reference___returns_false: "&Vec<u8>", "&mut _", false
+        ty_inferred_by___with_lifetime_wildcard___returns_true: "&'a str", "&'_ str", true
+        ty_inferred_by___with_lifetime_wildcard_on_elided_lifetime___returns_true: "&str", "&'_ str", true
+        ty_inferred_by___with_lifetime_wildcard_and_different_type___returns_false: "&'a str", "&'_ String", false
+        ty_inferred_by___with_different_lifetimes___returns_false: "&'a str", "&'b str", false
+        ty_inferred_by___with_elided_lifetime_on_pattern___returns_true: "&'a str", "&str", true
+        ty_inferred_by___with_named_lifetime_on_pattern_and_elided_on_field___returns_false: "&str", "&'a str", false
+        ty_inferred_by___with_lifetime_wildcard_in_generics___returns_true: "Cow<'a, str>", "Cow<'_, str>", true
+        ty_inferred_by___with_different_lifetimes_in_generics___returns_false: "Cow<'a, str>", "Cow<'b, str>", false
+        ty_inferred_by___with_reference_wildcard_in_generics___returns_true: "Option<&'a i32>", "Option<&_>", true
+        ty_inferred_by___with_reference_wildcard_in_generics_on_mutable_reference___returns_false: "Option<&mut i32>", "Option<&_>", false
+        ty_inferred_by___with_value_compared_to_reference_wildcard___returns_false: "i32", "&_", false
+        ty_inferred_by___with_wildcard_before_nested_generics___returns_true: "HashMap<String, Vec<(A, B)>>", "HashMap<_, Vec<(A, B)>>", true
+        ty_inferred_by___with_wildcard_over_nested_generics___returns_true: "HashMap<String, Vec<(A, B)>>", "HashMap<String, _>", true
+        ty_inferred_by___with_wildcard_inside_nested_tuple___returns_true: "HashMap<String, Vec<(A, B)>>", "HashMap<_, Vec<(_, B)>>", true
+        ty_inferred_by___with_wildcard_inside_nested_tuple_and_wrong_type___returns_false: "HashMap<String, Vec<(A, B)>>", "HashMap<_, Vec<(_, A)>>", false
+        ty_inferred_by___with_wildcard_over_generic_type___returns_true: "Option<Vec<i32>>", "Option<_>", true
+        ty_inferred_by___with_tuple_of_different_length___returns_false: "(i32, i32, i32)", "(_, _)", false
+        ty_inferred_by___with_wildcard_in_array___returns_true: "[Option<u8>; 4]", "[Option<_>; 4]", true
+        ty_inferred_by___with_array_of_different_length___returns_false: "[u8; 4]", "[u8; 5]", false
+        ty_inferred_by___with_wildcard_in_slice___returns_true: "&'a [Option<u8>]", "&[_]", true
+        ty_inferred_by___with_wildcard_in_pointer___returns_true: "*const (u8, u8)", "*const (_, u8)", true
+        ty_inferred_by___with_pointer_of_different_mutability___returns_false: "*const u8", "*mut _", false
+        ty_inferred_by___with_wildcards_in_bare_fn___returns_true: "fn(i32, &str) -> Option<u8>", "fn(_, &str) -> Option<_>", true
+        ty_inferred_by___with_bare_fn_of_different_arity___returns_false: "fn(i32) -> u8", "fn(_, _) -> u8", false
+        ty_inferred_by___with_wildcards_in_fn_trait___returns_true: "Box<dyn Fn(i32) -> Vec<u8> + Send>", "Box<dyn Fn(_) -> _ + Send>", true
+        ty_inferred_by___with_fn_trait_of_different_bounds___returns_false: "Box<dyn Fn(i32) + Send>", "Box<dyn Fn(_)>", false
+        ty_inferred_by___with_wildcard_in_associated_type___returns_true: "Box<dyn Iterator<Item = (u8, u8)>>", "Box<dyn Iterator<Item = (_, u8)>>", true
+        ty_inferred_by___with_wildcard_in_qualified_path___returns_true: "<Vec<u8> as IntoIterator>::Item", "<_ as IntoIterator>::Item", true
+        ty_inferred_by___with_parenthesized_type___returns_true: "&'a (dyn Display + Send)", "&(dyn Display + Send)", true
+        ty_inferred_by___with_different_path_lengths___returns_false: "fmt::Error", "std::fmt::Error", false
+    }
+
+    macro_rules! type_pattern_tests {
+        ($($name:ident: $str1:expr, $str2:expr, $expected:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_eq!(
+                    ty_inferred_by(
+                        &syn::parse_str($str1).unwrap(),
+                        &syn::parse::Parser::parse_str(parse_type_pattern, $str2).unwrap()
+                    ),
+                    $expected
+                );
+            }
+        )*
+        }
+    }
+
+    type_pattern_tests! {
+        type_pattern___with_trait_wildcard_in_box___returns_true: "Box<dyn Display>", "Box<dyn _>", true
+        type_pattern___with_trait_wildcard_in_reference___returns_true: "&'a dyn Display", "&dyn _", true
+        type_pattern___with_trait_wildcard_on_concrete_type___returns_false: "Box<i32>", "Box<dyn _>", false
+        type_pattern___with_trait_wildcard_in_fn_trait___returns_true: "Box<dyn Fn(i32) + Send>", "Box<dyn _>", true
+        type_pattern___with_fn_type___parses_until_top_level_comma: "fn(i32) -> Option<i32>", "fn(i32) -> Option<i32>", true
     }
 
     macro_rules! are_matching_types_tests {
@@ -175,5 +631,242 @@ mod test {
     are_matching_types_tests! {
         are_matching_types___with_matching_wildcards_in_both_sides___returns_true: "Result<i32, _>", "Result<_, i32>", true
         are_matching_types___between_wildcard_and_any_other_type___returns_true: "_", "Option<f32>", true
+        are_matching_types___with_wildcards_in_both_sides_of_tuples___returns_true: "(_, i32)", "(f32, _)", true
+        are_matching_types___with_lifetime_wildcard_in_both_sides___returns_true: "Cow<'_, str>", "Cow<'a, str>", true
+        are_matching_types___with_different_types___returns_false: "Option<i32>", "Option<f32>", false
+    }
+
+    macro_rules! is_type_param_tests {
+        ($($name:ident: $str1:expr, $params:expr, $expected:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let type_params: Vec<syn::Ident> = $params
+                    .iter()
+                    .map(|param: &&str| syn::parse_str(param).unwrap())
+                    .collect();
+                assert_eq!(
+                    is_type_param(&syn::parse_str($str1).unwrap(), &type_params),
+                    $expected
+                );
+            }
+        )*
+        }
+    }
+
+    is_type_param_tests! {
+        is_type_param___with_declared_param___returns_true: "T", ["T", "U"], true
+        is_type_param___with_parenthesized_param___returns_true: "(U)", ["T", "U"], true
+        is_type_param___with_undeclared_param___returns_false: "V", ["T", "U"], false
+        is_type_param___with_param_inside_generic___returns_false: "Option<T>", ["T"], false
+        is_type_param___with_param_inside_reference___returns_false: "&'a T", ["T"], false
+        is_type_param___with_associated_type___returns_false: "T::Item", ["T"], false
+        is_type_param___with_no_params___returns_false: "T", [] as [&str; 0], false
+    }
+
+    macro_rules! mentions_generic_params_tests {
+        ($($name:ident: $str1:expr, $params:expr, $expected:expr)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let params: Vec<syn::Ident> = $params
+                    .iter()
+                    .map(|param: &&str| syn::parse_str(param).unwrap())
+                    .collect();
+                assert_eq!(
+                    mentions_generic_params(&syn::parse_str($str1).unwrap(), &params),
+                    $expected
+                );
+            }
+        )*
+        }
+    }
+
+    mentions_generic_params_tests! {
+        mentions_generic_params___with_param___returns_true: "T", ["T"], true
+        mentions_generic_params___with_param_inside_generic___returns_true: "Inner<'a, Vec<T>>", ["T"], true
+        mentions_generic_params___with_const_param_inside_array___returns_true: "[u8; N]", ["N"], true
+        mentions_generic_params___with_associated_type___returns_true: "<T as Trait>::Item", ["T"], true
+        mentions_generic_params___with_lifetime_named_like_param___returns_false: "Inner<'T>", ["T"], false
+        mentions_generic_params___with_path_segment_named_like_param___returns_false: "module::T", ["T"], false
+        mentions_generic_params___with_undeclared_param___returns_false: "Inner<U>", ["T"], false
+    }
+
+    #[test]
+    fn erase_lifetimes___with_nested_lifetimes___replaces_all_of_them_with_static() {
+        let ty: Type = syn::parse_str("Middle<'a, Cow<'_, str>, [&'b u8; 2]>").unwrap();
+        assert_eq!(
+            type_pattern_to_string(&erase_lifetimes(&ty)),
+            quote! { Middle<'static, Cow<'static, str>, [&'static u8; 2]> }.to_string()
+        );
+    }
+
+    #[test]
+    fn name_elided_lifetimes___with_elided_and_named_lifetimes___names_only_elided_ones() {
+        let ty: Type = syn::parse_str("(&'static str, &&mut Cow<'_, [&'a u8]>)").unwrap();
+        let lifetime: Lifetime = syn::parse_str("'l").unwrap();
+        assert_eq!(
+            type_pattern_to_string(&name_elided_lifetimes(&ty, &lifetime)),
+            quote! { (&'static str, &'l &'l mut Cow<'l, [&'a u8]>) }.to_string()
+        );
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        #[derive(Clone, Debug)]
+        enum TyTree {
+            Leaf(&'static str),
+            Node(&'static str, Vec<TyTree>),
+        }
+
+        const LEAVES: &[&str] = &["i32", "u8", "String", "T", "std::fmt::Error"];
+
+        // Each '{}' in a template is replaced by one child.
+        const TEMPLATES: &[(&str, usize)] = &[
+            ("Option<{}>", 1),
+            ("Result<{}, {}>", 2),
+            ("HashMap<{}, Vec<{}>>", 2),
+            ("({}, {})", 2),
+            ("({}, {}, {})", 3),
+            ("[{}; 4]", 1),
+            ("&'a [{}]", 1),
+            ("&'a {}", 1),
+            ("&'a mut {}", 1),
+            ("*const {}", 1),
+            ("fn({}) -> {}", 2),
+            ("Box<dyn Fn({}) -> {} + Send>", 2),
+            ("Box<dyn Iterator<Item = {}>>", 1),
+        ];
+
+        impl TyTree {
+            fn render(&self) -> String {
+                match self {
+                    TyTree::Leaf(leaf) => leaf.to_string(),
+                    TyTree::Node(template, children) => {
+                        children.iter().fold(template.to_string(), |acc, child| {
+                            acc.replacen("{}", &child.render(), 1)
+                        })
+                    }
+                }
+            }
+
+            fn size(&self) -> usize {
+                match self {
+                    TyTree::Leaf(_) => 1,
+                    TyTree::Node(_, children) => {
+                        1 + children.iter().map(TyTree::size).sum::<usize>()
+                    }
+                }
+            }
+
+            // Replaces the node at the given preorder position by the result of `replacement`.
+            fn replace(&self, position: usize, replacement: &dyn Fn(&TyTree) -> TyTree) -> TyTree {
+                self.replace_from(&mut { position }, replacement)
+            }
+
+            fn replace_from(
+                &self,
+                position: &mut usize,
+                replacement: &dyn Fn(&TyTree) -> TyTree,
+            ) -> TyTree {
+                if *position == 0 {
+                    *position = usize::MAX;
+                    return replacement(self);
+                }
+                *position = position.wrapping_sub(1);
+                match self {
+                    TyTree::Leaf(_) => self.clone(),
+                    TyTree::Node(template, children) => TyTree::Node(
+                        template,
+                        children
+                            .iter()
+                            .map(|child| child.replace_from(position, replacement))
+                            .collect(),
+                    ),
+                }
+            }
+        }
+
+        fn ty_tree() -> impl Strategy<Value = TyTree> {
+            let leaf = proptest::sample::select(LEAVES).prop_map(TyTree::Leaf);
+            leaf.prop_recursive(4, 24, 3, |inner| {
+                (
+                    proptest::sample::select(TEMPLATES),
+                    proptest::collection::vec(inner, 3),
+                )
+                    .prop_map(|((template, arity), mut children)| {
+                        children.truncate(arity);
+                        TyTree::Node(template, children)
+                    })
+            })
+        }
+
+        fn parse(text: &str) -> Type {
+            syn::parse_str(text).unwrap()
+        }
+
+        proptest! {
+            #[test]
+            fn any_type___matches_itself(tree in ty_tree()) {
+                let ty = parse(&tree.render());
+                prop_assert!(ty_inferred_by(&ty, &ty));
+                prop_assert!(are_matching_types(&ty, &ty));
+            }
+
+            #[test]
+            fn any_type___matches_pattern_with_any_subtree_replaced_by_wildcard(
+                tree in ty_tree(),
+                position in any::<usize>(),
+            ) {
+                let pattern = tree.replace(position % tree.size(), &|_| TyTree::Leaf("_"));
+                let ty = parse(&tree.render());
+                let pattern = parse(&pattern.render());
+                prop_assert!(ty_inferred_by(&ty, &pattern));
+                prop_assert!(are_matching_types(&ty, &pattern));
+                prop_assert!(are_matching_types(&pattern, &ty));
+            }
+
+            #[test]
+            fn any_type___does_not_match_pattern_with_a_different_leaf(
+                tree in ty_tree(),
+                position in any::<usize>(),
+            ) {
+                let pattern = tree.replace(position % tree.size(), &|node| match node {
+                    TyTree::Leaf("bool") => TyTree::Leaf("char"),
+                    TyTree::Leaf(_) => TyTree::Leaf("bool"),
+                    node => node.clone(),
+                });
+                let ty = parse(&tree.render());
+                let pattern_ty = parse(&pattern.render());
+                prop_assert_eq!(
+                    ty_inferred_by(&ty, &pattern_ty),
+                    tree.render() == pattern.render()
+                );
+            }
+
+            #[test]
+            fn are_matching_types___is_symmetric(left in ty_tree(), right in ty_tree()) {
+                let (left, right) = (parse(&left.render()), parse(&right.render()));
+                prop_assert_eq!(are_matching_types(&left, &right), are_matching_types(&right, &left));
+            }
+
+            #[test]
+            fn table_cases___keep_their_result_within_any_context(
+                tree in ty_tree(),
+                position in any::<usize>(),
+                case in proptest::sample::select(TY_INFERRED_BY_CASES),
+            ) {
+                let (field, pattern, expected) = case;
+                let wrap = |text: &'static str| {
+                    tree.replace(position % tree.size(), &|_| TyTree::Leaf(text)).render()
+                };
+                prop_assert_eq!(
+                    ty_inferred_by(&parse(&wrap(field)), &parse(&wrap(pattern))),
+                    expected
+                );
+            }
+        }
     }
 }
@@ -1,13 +1,163 @@
-pub trait OptionExtensions<T> {
-    fn insert_stable(&mut self, value: T) -> &mut T;
+use syn::ext::IdentExt;
+use syn::parse::Result;
+use syn::{Error, Member};
+
+pub fn member_to_string(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.unraw().to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
 }
 
-impl<T> OptionExtensions<T> for Option<T> {
-    fn insert_stable(&mut self, value: T) -> &mut T {
-        *self = Some(value);
+pub trait ErrorAccumulator {
+    fn push_error(&mut self, error: Error);
+    fn into_result<T>(self, result: Result<T>) -> Result<T>;
+}
+
+impl ErrorAccumulator for Option<Error> {
+    fn push_error(&mut self, error: Error) {
         match self {
-            Some(value) => value,
-            None => unreachable!(),
+            Some(errors) => errors.combine(error),
+            None => *self = Some(error),
         }
     }
+
+    fn into_result<T>(self, result: Result<T>) -> Result<T> {
+        match (self, result) {
+            (None, result) => result,
+            (Some(errors), Ok(_)) => Err(errors),
+            (Some(mut errors), Err(error)) => {
+                errors.combine(error);
+                Err(errors)
+            }
+        }
+    }
+}
+
+pub fn make_suggestion<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    candidates_kind: &str,
+) -> String {
+    let candidates: Vec<&str> = candidates.into_iter().collect();
+    if candidates.is_empty() {
+        return format!(". There are no {} available", candidates_kind);
+    }
+
+    let available = candidates
+        .iter()
+        .map(|candidate| format!("'{}'", candidate))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let max_distance = name.chars().count() / 3 + 1;
+    let closest = candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance);
+
+    match closest {
+        Some((_, closest)) => format!(
+            ". Did you mean '{}'? Available {}: {}",
+            closest, candidates_kind, available
+        ),
+        None => format!(". Available {}: {}", candidates_kind, available),
+    }
+}
+
+// Optimal string alignment distance: Levenshtein plus transpositions of adjacent characters, so
+// typos like 'nmaes' are considered as close to 'names' as a single wrong character.
+pub fn edit_distance(left: &str, right: &str) -> usize {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let mut distances = vec![vec![0; right.len() + 1]; left.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=left.len() {
+        for j in 1..=right.len() {
+            let cost = if left[i - 1] == right[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[left.len()][right.len()]
+}
+
+// Glob-like matching where '*' matches any sequence of characters and '?' matches exactly one.
+pub fn matches_name_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let mut matches = vec![vec![false; name.len() + 1]; pattern.len() + 1];
+    matches[0][0] = true;
+    for i in 1..=pattern.len() {
+        matches[i][0] = matches[i - 1][0] && pattern[i - 1] == '*';
+        for j in 1..=name.len() {
+            matches[i][j] = match pattern[i - 1] {
+                '*' => matches[i - 1][j] || matches[i][j - 1],
+                '?' => matches[i - 1][j - 1],
+                c => matches[i - 1][j - 1] && c == name[j - 1],
+            };
+        }
+    }
+    matches[pattern.len()][name.len()]
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edit_distance___with_typos___counts_single_edits() {
+        assert_eq!(edit_distance("names", "names"), 0);
+        assert_eq!(edit_distance("nmaes", "names"), 1);
+        assert_eq!(edit_distance("get_name", "get_names"), 1);
+        assert_eq!(edit_distance("cats", "cast"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn make_suggestion___with_close_candidate___suggests_it() {
+        assert_eq!(
+            make_suggestion("get_name", vec!["get_names", "get_ids"], "methods"),
+            ". Did you mean 'get_names'? Available methods: 'get_names', 'get_ids'"
+        );
+    }
+
+    #[test]
+    fn make_suggestion___without_close_candidate___lists_available() {
+        assert_eq!(
+            make_suggestion("another_array", vec!["my_array"], "methods"),
+            ". Available methods: 'my_array'"
+        );
+        assert_eq!(
+            make_suggestion("my_array", vec![], "methods"),
+            ". There are no methods available"
+        );
+    }
+
+    #[test]
+    fn matches_name_pattern___with_wildcards___matches_expected_names() {
+        assert!(matches_name_pattern("*_price", "water_price"));
+        assert!(matches_name_pattern("*_price", "_price"));
+        assert!(!matches_name_pattern("*_price", "water_prices"));
+        assert!(matches_name_pattern("enable_*", "enable_gpu"));
+        assert!(!matches_name_pattern("enable_*", "disable_gpu"));
+        assert!(matches_name_pattern("*_timeout_*", "read_timeout_ms"));
+        assert!(matches_name_pattern("field_?", "field_1"));
+        assert!(!matches_name_pattern("field_?", "field_10"));
+        assert!(matches_name_pattern("exact", "exact"));
+        assert!(!matches_name_pattern("exact", "exactly"));
+        assert!(matches_name_pattern("*", "0"));
+    }
 }
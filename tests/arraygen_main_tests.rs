@@ -106,6 +106,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_arraygen___without_includes___generates_0_length_arrays() {
         #[derive(Arraygen)]
         #[gen_array(fn foo: i32)]
@@ -118,4 +119,20 @@ mod tests {
 
         assert_eq!(test.foo().len(), 0);
     }
+
+    #[test]
+    #[deny(deprecated)]
+    fn test_arraygen___with_allow_empty___generates_0_length_arrays_without_warnings() {
+        #[derive(Arraygen)]
+        #[gen_array(fn foo: &i32, allow_empty)]
+        #[gen_array(fn bar: &i32, implicit_select_all: i32)]
+        struct Sut {
+            baz: i32,
+        }
+
+        let test = Sut { baz: 2 };
+
+        assert_eq!(test.foo().len(), 0);
+        assert_eq!(test.bar(), [&2]);
+    }
 }
@@ -0,0 +1,77 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_by_value___with_owned_strings___moves_fields_out() {
+        #[derive(Arraygen)]
+        #[gen_array(fn into_names: String, by_value)]
+        struct Person {
+            #[in_array(into_names)]
+            first_name: String,
+            #[in_array(into_names)]
+            last_name: String,
+            age: u8,
+        }
+
+        let person = Person {
+            first_name: "Ada".into(),
+            last_name: "Lovelace".into(),
+            age: 36,
+        };
+
+        assert_eq!(person.into_names(), ["Ada", "Lovelace"]);
+    }
+
+    #[test]
+    fn test_by_value___with_boxed_trait_objects___moves_fields_out() {
+        trait Animal {
+            fn talk(&self) -> &'static str;
+        }
+        struct Dog;
+        impl Animal for Dog {
+            fn talk(&self) -> &'static str {
+                "bark"
+            }
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn into_animals: Box<dyn Animal>, by_value, implicit_select_all: Box<_>)]
+        struct Farm {
+            first: Box<Dog>,
+            second: Box<dyn Animal>,
+        }
+
+        let farm = Farm {
+            first: Box::new(Dog),
+            second: Box::new(Dog),
+        };
+
+        let animals: Vec<Box<dyn Animal>> = farm.into_animals().into();
+        assert_eq!(animals.iter().map(|a| a.talk()).collect::<Vec<_>>(), ["bark", "bark"]);
+    }
+
+    #[test]
+    fn test_by_value___on_enum_with_names___moves_fields_of_active_variant() {
+        #[derive(Arraygen)]
+        #[gen_array(fn into_parts: String, with_names, by_value, implicit_select_all: String)]
+        enum Name {
+            Full { first: String, last: String },
+            Nick(String),
+        }
+
+        let actual = Name::Full {
+            first: "Ada".into(),
+            last: "Lovelace".into(),
+        };
+
+        assert_eq!(
+            actual.into_parts(),
+            [("first", "Ada".to_string()), ("last", "Lovelace".to_string())]
+        );
+        assert_eq!(Name::Nick("Ada".into()).into_parts(), [("0", "Ada".to_string())]);
+    }
+}
@@ -1,5 +1,6 @@
 extern crate arraygen;
 
+#[allow(clippy::excessive_precision)]
 #[allow(non_snake_case)]
 mod tests {
     use arraygen::Arraygen;
@@ -0,0 +1,108 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    // `cfg(test)` is always enabled and `cfg(not(test))` is always disabled in this crate, which
+    // lets these tests cover both outcomes without depending on cargo features.
+
+    #[test]
+    fn test_cfg___with_in_array_on_gated_fields___includes_only_enabled_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn get_values: &i32, names = value_names)]
+        struct Test {
+            #[in_array(get_values)]
+            first: i32,
+            #[cfg(not(test))]
+            #[in_array(get_values)]
+            disabled: i32,
+            #[cfg(test)]
+            #[in_array(get_values)]
+            enabled: i32,
+        }
+
+        let actual = Test {
+            first: 1,
+            enabled: 3,
+        };
+
+        assert_eq!(actual.get_values(), [&1, &3]);
+        assert_eq!(Test::value_names(), ["first", "enabled"]);
+        assert_eq!(Test::GET_VALUES_LEN, 2);
+    }
+
+    #[test]
+    fn test_cfg___with_implicit_select_all_on_gated_fields___includes_only_enabled_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn get_values: &mut f32, implicit_select_all: f32, with_names)]
+        struct Test {
+            #[cfg(not(test))]
+            disabled: f32,
+            first: f32,
+            #[cfg(test)]
+            enabled: f32,
+        }
+
+        let mut actual = Test {
+            first: 1.0,
+            enabled: 2.0,
+        };
+
+        for (_, value) in actual.get_values().iter_mut() {
+            **value *= 10.0;
+        }
+
+        assert_eq!(actual.first, 10.0);
+        assert_eq!(actual.enabled, 20.0);
+        assert_eq!(actual.get_values().map(|(name, _)| name), ["first", "enabled"]);
+    }
+
+    #[test]
+    fn test_cfg___with_only_disabled_fields___returns_empty_array() {
+        #[derive(Arraygen)]
+        #[gen_array(fn get_values: i32, implicit_select_all: i32, by_value, allow_empty)]
+        struct Test {
+            #[cfg(not(test))]
+            disabled: i32,
+            text: String,
+        }
+
+        let actual = Test {
+            text: String::new(),
+        };
+
+        assert_eq!(actual.get_values().len(), 0);
+    }
+
+    #[test]
+    fn test_cfg___with_gated_positional_fields___uses_indices_of_enabled_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn get_values: &i32, implicit_select_all: i32)]
+        struct Test(#[cfg(not(test))] i32, i32, #[cfg(test)] i32);
+
+        let actual = Test(1, 2);
+
+        assert_eq!(actual.get_values(), [&1, &2]);
+    }
+
+    #[test]
+    fn test_cfg___with_gated_variants_and_fields___includes_only_enabled_ones() {
+        #[derive(Arraygen)]
+        #[gen_array(fn get_values: &u8, implicit_select_all: u8)]
+        enum Test {
+            #[cfg(not(test))]
+            Disabled { a: u8 },
+            Mixed {
+                #[cfg(not(test))]
+                disabled: u8,
+                enabled: u8,
+            },
+        }
+
+        let actual = Test::Mixed { enabled: 1 };
+
+        assert_eq!(actual.get_values(), vec![&1]);
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: &String, by_value)] //~ERROR 8:35: 8:43: gen_array method 'my_array' can't contain by_value clause when returning references
+struct Test {
+    #[in_array(my_array)]
+    foo: String,
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: &i32, implicit_select_all: i32, names = my_names, names = other_names)] //~ERROR 8:76: 8:81: clause 'names' is declared more than once
+#[gen_array(fn other_array: &i32, implicit_select_all: i32, with_names, with_names)] //~ERROR 9:73: 9:83: clause 'with_names' is declared more than once
+struct Test {
+    a: i32,
+}
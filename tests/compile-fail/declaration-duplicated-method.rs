@@ -0,0 +1,12 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32)]
+#[gen_array(fn my_array: f32)] //~ERROR 9:16: 9:24: gen_array method 'my_array' is declared more than once
+struct Test {
+    foo: i32,
+}
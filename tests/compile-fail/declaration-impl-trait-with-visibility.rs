@@ -0,0 +1,15 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+trait HasValues {
+    fn values(&self) -> [i32; 1];
+}
+
+#[derive(Arraygen)]
+#[gen_array(pub fn values: i32, implicit_select_all: i32, impl_trait = HasValues)] //~ERROR 12:13: 12:16: gen_array method 'values' can't have visibility because it implements trait 'HasValues'
+struct Test {
+    a: i32,
+}
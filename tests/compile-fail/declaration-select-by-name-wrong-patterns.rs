@@ -0,0 +1,12 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32, select_by_name: "foo", "")] //~ERROR 8:54: 8:56: name pattern can't be empty
+#[gen_array(fn other_array: i32, select_by_name { override_implicit }: "foo")] //~ERROR 9:34: 9:48: gen_array method 'other_array' contains select_by_name clause with forbidden decorator 'override_implicit'
+struct Test {
+    foo: i32,
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+struct Bytes;
+
+#[derive(Arraygen)]
+#[gen_array(fn bytes: u8, implicit_select_all { try_into }: i32, tag = Bytes)] //~ERROR 10:72: 10:77: gen_array method 'bytes' can't contain tag clause because it returns a Result
+struct Test {
+    a: i32,
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32, nmaes = my_names)] //~ERROR 8:31: 8:36: clause 'nmaes' not recognised. Did you mean 'names'? Available clauses: 'implicit_select_all', 'names', 'with_names', 'by_value', 'allow_empty', 'except', 'select_by_name', 'select_vis', 'generic_params', 'tag', 'impl_trait', 'get', 'get_by_name'
+struct Test {
+    foo: i32,
+}
@@ -5,7 +5,4 @@ extern crate arraygen;
 use arraygen::Arraygen;
 
 #[derive(Arraygen)]
-struct Empty1; //~ERROR 8:14: 8:15: derive 'Arraygen' should only be used with braced structs
-
-#[derive(Arraygen)]
-struct Empty2(i32, f32); //~ERROR 11:14: 11:15: derive 'Arraygen' should only be used with braced structs
+struct Empty1; //~ERROR 8:14: 8:15: derive 'Arraygen' should only be used with braced or tuple structs
@@ -0,0 +1,8 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+union Empty2{a: i32} //~ERROR 8:1: 8:6: derive 'Arraygen' should only be used with braced or tuple structs, or with enums
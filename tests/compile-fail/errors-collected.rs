@@ -0,0 +1,17 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: f32, implicit_select_all: f32, f32)] //~ERROR 8:57: 8:60: gen_array method 'my_array' contains implicit_select_all clause with duplicated 'f32' type
+#[gen_array(fn other_array: f32, allow_empty, allow_empty)] //~ERROR 9:47: 9:58: clause 'allow_empty' is declared more than once
+struct Test {
+    #[in_array(my_array)] //~ERROR 11:16: 11:24: Field 'first' is already included in gen_array method 'my_array'
+    pub first: f32,
+    #[in_array(unknown_array)] //~ERROR 13:16: 13:29: gen_array method 'unknown_array' not present but used by field 'second'
+    pub second: f32,
+    #[in_array(other_array { wrong })] //~ERROR 15:30: 15:35: in_array doesn't allow 'wrong' as decorator
+    pub third: f32,
+}
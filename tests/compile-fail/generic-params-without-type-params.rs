@@ -0,0 +1,12 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32, generic_params)] //~ERROR 8:31: 8:45: clause 'generic_params' can't be used because 'Test' has no type parameters
+struct Test<'a> {
+    a: i32,
+    b: &'a i32,
+}
@@ -53,7 +53,7 @@ struct Implicit8 {
 }
 
 #[derive(Arraygen)]
-#[gen_array(fn my_array: Result<Option<f32>, Option<f32>>, implicit_select_all:  Result<Option<_>, Option<f32>>, Result<Option<f32>, Option<_>>)] //~ERROR 56:114: 56:144: gen_array method 'my_array' contains implicit_select_all clause with duplicated 'Result < Option < f32 >, Option < _ > >' type
+#[gen_array(fn my_array: Result<Option<f32>, Option<f32>>, implicit_select_all:  Result<Option<_>, Option<f32>>, Result<Option<f32>, Option<_>>)] //~ERROR 56:114: 56:144: gen_array method 'my_array' contains implicit_select_all clause with duplicated 'Result < Option < f32 > , Option < _ > >' type
 struct Implicit9 {
     pub value: Result<Option<f32>, Option<f32>>,
 }
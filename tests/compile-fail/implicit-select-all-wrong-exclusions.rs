@@ -0,0 +1,15 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32, implicit_select_all: i32, except: foo, bar)] //~ERROR 8:70: 8:73: Field 'bar' excluded by except clause of gen_array method 'my_array' is not present
+#[gen_array(fn other_array: i32, except: foo)] //~ERROR 9:34: 9:40: gen_array method 'other_array' contains except clause without implicit_select_all, select_by_name, select_vis or generic_params clauses
+#[gen_array(fn skipped_array: i32, implicit_select_all { skip }: i32)] //~ERROR 10:36: 10:55: gen_array method 'skipped_array' contains implicit_select_all clause with forbidden decorator 'skip'
+struct Test {
+    foo: i32,
+    #[in_array(my_array { skip, cast })] //~ERROR 13:27: 13:31: decorator 'skip' can't be combined with other decorators
+    baz: i32,
+}
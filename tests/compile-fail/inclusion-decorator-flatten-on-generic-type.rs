@@ -0,0 +1,21 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn all: &i32, implicit_select_all: i32)]
+struct Inner<T> {
+    a: i32,
+    b: T,
+}
+
+#[derive(Arraygen)]
+#[gen_array(fn all: &i32)]
+struct Test<T, const N: usize> {
+    #[in_array(all { flatten })]
+    generic: Inner<T>, //~ERROR 18:14: 18:22: decorator 'flatten' can't be used in gen_array method 'all' on field 'generic' because its type depends on the generic parameters of 'Test'
+    #[in_array(all { flatten })]
+    sized: Inner<[u8; N]>, //~ERROR 20:12: 20:26: decorator 'flatten' can't be used in gen_array method 'all' on field 'sized' because its type depends on the generic parameters of 'Test'
+}
@@ -0,0 +1,19 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: f32, implicit_select_all { flatten }: f32)] //~ERROR 8:31: 8:50: gen_array method 'my_array' contains implicit_select_all clause with forbidden decorator 'flatten'
+#[gen_array(fn named: f32, names = my_names)]
+#[gen_array(fn casted: f32)]
+#[gen_array(fn by_name: f32, get_by_name = value_of)]
+struct Test {
+    #[in_array(named { flatten })] //~ERROR 13:16: 13:21: decorator 'flatten' can't be used in gen_array method 'named' because it contains names clause 'my_names'
+    a: f32,
+    #[in_array(casted { flatten, cast })] //~ERROR 15:25: 15:32: decorator 'flatten' can't be combined with 'cast'
+    b: f32,
+    #[in_array(by_name { flatten })] //~ERROR 17:16: 17:23: decorator 'flatten' can't be used in gen_array method 'by_name' because it contains get_by_name clause 'value_of'
+    c: f32,
+}
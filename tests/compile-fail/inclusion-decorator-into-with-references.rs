@@ -0,0 +1,12 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: &i64, implicit_select_all { try_into }: u8)] //~ERROR 8:32: 8:51: decorator 'try_into' can't be used in gen_array method 'my_array' because it returns references
+struct Test {
+    #[in_array(my_array { into })] //~ERROR 10:16: 10:24: decorator 'into' can't be used in gen_array method 'my_array' because it returns references
+    foo: i32,
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn ints: &i32, implicit_select_all: i32)]
+#[gen_array(fn explicit: &i32)]
+struct Test {
+    #[in_array(ints { skip })]
+    a: i32,
+    #[in_array(ints { skip })] //~ERROR 13:16: 13:20: Field 'b' is not implicitly selected by gen_array method 'ints'
+    b: f32,
+    #[in_array(explicit { skip })] //~ERROR 15:16: 15:24: Field 'c' is not implicitly selected by gen_array method 'explicit'
+    c: i32,
+}
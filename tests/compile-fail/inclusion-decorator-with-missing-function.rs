@@ -0,0 +1,14 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: String)]
+struct Test {
+    #[in_array(my_array { with })] //~ERROR 10:27: 10:31: decorator 'with' requires a function, like 'with = path::to_function'
+    foo: i32,
+    #[in_array(my_array { cast = i32 })] //~ERROR 12:34: 12:37: decorator 'cast' doesn't accept a value
+    bar: i32,
+}
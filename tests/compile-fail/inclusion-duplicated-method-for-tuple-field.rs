@@ -0,0 +1,9 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(pub fn my_array: i32)]
+struct Test(i32, #[in_array(my_array, my_array)] i32); //~ERROR 9:39: 9:47: Field '1' is already included in gen_array method 'my_array'
@@ -0,0 +1,15 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(pub fn my_array: i32, implicit_select_all: i32)]
+enum Test {
+    A(i32),
+    B {
+        #[in_array(my_array)] //~ERROR 12:20: 12:28: Field 'foo' is already included in gen_array method 'my_array'
+        foo: i32,
+    },
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(pub fn get_names: &String)]
+#[gen_array(pub fn get_ids: &u32)]
+struct Test{
+    #[in_array(get_name)] //~ERROR 11:16: 11:24: gen_array method 'get_name' not present but used by field 'name'. Did you mean 'get_names'? Available methods: 'get_names', 'get_ids'
+    name: String,
+    #[in_array(get_ids { cats })] //~ERROR 13:26: 13:30: in_array doesn't allow 'cats' as decorator here. Did you mean 'cast'? Available decorators: 'override_implicit', 'cast', 'unsafe_transmute', 'into', 'try_into', 'with', 'skip', 'flatten'
+    id: u32,
+}
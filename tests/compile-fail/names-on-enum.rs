@@ -0,0 +1,11 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32, names = my_names)] //~ERROR 8:39: 8:47: clause 'names' is not supported on enums
+enum Test {
+    A(#[in_array(my_array)] i32),
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn my_array: i32, select_vis: pub)] //~ERROR 8:43: 8:46: clause 'select_vis' is not supported on enums, since their fields have no visibility
+enum Test {
+    A(i32),
+}
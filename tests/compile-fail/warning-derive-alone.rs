@@ -0,0 +1,14 @@
+#![no_main]
+#![deny(deprecated)]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+struct Empty{} //~ERROR 9:8: 9:13: use of deprecated unit struct `_::ArraygenWarning`: The struct 'Empty' derives 'Arraygen' but does not contain any 'gen_array' attribute, so 'Arraygen' does nothing.
+
+#[derive(Arraygen)]
+enum Nothing { //~ERROR 12:6: 12:13: use of deprecated unit struct `_::ArraygenWarning`: The enum 'Nothing' derives 'Arraygen' but does not contain any 'gen_array' attribute, so 'Arraygen' does nothing.
+    A,
+}
@@ -0,0 +1,21 @@
+#![no_main]
+#![deny(deprecated)]
+
+extern crate arraygen;
+
+use arraygen::Arraygen;
+
+#[derive(Arraygen)]
+#[gen_array(fn empty: i32)] //~ERROR 9:16: 9:21: use of deprecated unit struct `_::ArraygenWarning`: Method 'empty' from struct 'Test' returns an empty array.
+#[gen_array(fn allowed: i32, allow_empty)]
+#[gen_array(fn filled: i32)]
+struct Test{
+    #[in_array(filled)]
+    foo: i32,
+}
+
+#[derive(Arraygen)]
+#[gen_array(fn empty: i32)] //~ERROR 18:16: 18:21: use of deprecated unit struct `_::ArraygenWarning`: Method 'empty' from enum 'Choice' returns an empty list. Add the 'allow_empty' clause to 'gen_array' to silence this warning.
+enum Choice {
+    A(u8),
+}
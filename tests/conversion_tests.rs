@@ -0,0 +1,162 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+    use std::convert::TryFrom;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Meters(f64);
+
+    impl From<Meters> for f64 {
+        fn from(meters: Meters) -> f64 {
+            meters.0
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Percentage(u8);
+
+    impl TryFrom<i32> for Percentage {
+        type Error = String;
+
+        fn try_from(value: i32) -> Result<Self, Self::Error> {
+            if (0..=100).contains(&value) {
+                Ok(Percentage(value as u8))
+            } else {
+                Err(format!("{} is not a percentage", value))
+            }
+        }
+    }
+
+    #[test]
+    fn test_into_on_in_array___returns_converted_values() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: f64)]
+        struct Sut {
+            #[in_array(values { into })]
+            pub a: Meters,
+            #[in_array(values { into })]
+            pub b: f32,
+            #[in_array(values)]
+            pub c: f64,
+        }
+
+        let actual = Sut {
+            a: Meters(1.5),
+            b: 2.0,
+            c: 3.0,
+        };
+
+        assert_eq!(actual.values(), [1.5, 2.0, 3.0]);
+        assert_eq!(actual.a, Meters(1.5));
+    }
+
+    #[test]
+    fn test_into_on_implicit_select_all___returns_converted_values() {
+        #[derive(Arraygen)]
+        #[gen_array(fn texts: String, implicit_select_all { into }: &'static str, with_names)]
+        struct Sut {
+            pub a: &'static str,
+            pub b: &'static str,
+            pub c: i32,
+        }
+
+        let actual = Sut {
+            a: "foo",
+            b: "bar",
+            c: 3,
+        };
+
+        assert_eq!(
+            actual.texts(),
+            [("a", "foo".to_string()), ("b", "bar".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_into_with_by_value___moves_fields_without_cloning() {
+        struct NotClone(String);
+
+        impl From<NotClone> for String {
+            fn from(value: NotClone) -> String {
+                value.0
+            }
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn into_texts: String, implicit_select_all { into }: NotClone, by_value)]
+        struct Sut {
+            pub a: NotClone,
+            pub b: NotClone,
+        }
+
+        let actual = Sut {
+            a: NotClone("foo".to_string()),
+            b: NotClone("bar".to_string()),
+        };
+
+        assert_eq!(actual.into_texts(), ["foo", "bar"]);
+    }
+
+    #[test]
+    fn test_try_into_on_in_array___with_valid_values___returns_ok() {
+        #[derive(Arraygen)]
+        #[gen_array(fn percentages: Percentage, implicit_select_all { try_into }: i32)]
+        struct Sut {
+            pub a: i32,
+            pub b: i32,
+        }
+
+        let actual = Sut { a: 10, b: 100 };
+
+        assert_eq!(
+            actual.percentages().unwrap(),
+            [Percentage(10), Percentage(100)]
+        );
+        assert_eq!(Sut::PERCENTAGES_LEN, 2);
+    }
+
+    #[test]
+    fn test_try_into_on_in_array___with_invalid_value___returns_first_error() {
+        #[derive(Arraygen)]
+        #[gen_array(fn bytes: u8)]
+        struct Sut {
+            #[in_array(bytes { try_into })]
+            pub a: i32,
+            #[in_array(bytes { cast })]
+            pub b: i64,
+            #[in_array(bytes { try_into })]
+            pub c: u64,
+        }
+
+        let valid = Sut { a: 1, b: 2, c: 3 };
+        let invalid = Sut { a: 1, b: 2, c: 256 };
+
+        assert_eq!(valid.bytes().unwrap(), [1, 2, 3]);
+        assert_eq!(
+            invalid.bytes().unwrap_err().to_string(),
+            "out of range integral type conversion attempted"
+        );
+    }
+
+    #[test]
+    fn test_try_into_on_enum___returns_result_of_vec() {
+        #[derive(Arraygen)]
+        #[gen_array(fn percentages: Percentage, implicit_select_all { try_into }: i32)]
+        enum Sut {
+            One(i32),
+            Two { a: i32, b: i32 },
+        }
+
+        assert_eq!(Sut::One(5).percentages().unwrap(), vec![Percentage(5)]);
+        assert_eq!(
+            Sut::Two { a: 5, b: 500 }
+                .percentages()
+                .unwrap_err()
+                .to_string(),
+            "500 is not a percentage"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    trait Payload {
+        fn size(&self) -> usize;
+    }
+
+    impl Payload for u8 {
+        fn size(&self) -> usize {
+            1
+        }
+    }
+
+    impl Payload for u32 {
+        fn size(&self) -> usize {
+            4
+        }
+    }
+
+    #[test]
+    fn test_enum___with_in_array_on_variant_fields___returns_fields_of_active_variant() {
+        #[derive(Arraygen)]
+        #[gen_array(fn payloads: &dyn Payload)]
+        enum Message {
+            Small {
+                #[in_array(payloads)]
+                a: u8,
+                b: u8,
+            },
+            Big(#[in_array(payloads)] u32, #[in_array(payloads)] u8),
+            Empty,
+        }
+
+        let sizes = |message: &Message| -> Vec<usize> {
+            message.payloads().iter().map(|p| p.size()).collect()
+        };
+
+        assert_eq!(sizes(&Message::Small { a: 1, b: 2 }), [1]);
+        assert_eq!(sizes(&Message::Big(1, 2)), [4, 1]);
+        assert_eq!(sizes(&Message::Empty), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_enum___with_implicit_select_all_and_mut_refs___modifies_active_variant() {
+        #[derive(Arraygen, Debug, PartialEq)]
+        #[gen_array(fn numbers: &mut i32, implicit_select_all: i32)]
+        enum Shape {
+            Point(i32, i32),
+            Circle { x: i32, y: i32, radius: f32 },
+        }
+
+        let mut actual = Shape::Circle {
+            x: 1,
+            y: 2,
+            radius: 0.5,
+        };
+        for number in actual.numbers() {
+            *number *= 10;
+        }
+
+        assert_eq!(
+            actual,
+            Shape::Circle {
+                x: 10,
+                y: 20,
+                radius: 0.5
+            }
+        );
+        assert_eq!(Shape::Point(3, 4).numbers().len(), 2);
+    }
+
+    #[test]
+    fn test_enum___with_cast_and_discriminants___returns_casted_values() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: f64)]
+        #[repr(u8)]
+        enum Value {
+            Int(#[in_array(values { cast })] i32) = 1,
+            Float(#[in_array(values)] f64) = 2,
+        }
+
+        assert_eq!(Value::Int(3).values(), [3.0]);
+        assert_eq!(Value::Float(1.5).values(), [1.5]);
+    }
+
+    #[test]
+    fn test_enum___without_variants___compiles_correctly() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: i32, allow_empty)]
+        enum Never {}
+
+        fn _values(never: &Never) -> Vec<i32> {
+            never.values()
+        }
+    }
+}
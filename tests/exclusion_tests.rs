@@ -0,0 +1,93 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_except___with_wildcard___excludes_listed_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: i64, implicit_select_all { cast }: _, except: id, created_at)]
+        struct Sut {
+            pub id: u32,
+            pub a: i32,
+            pub created_at: u64,
+            pub b: u8,
+        }
+
+        let actual = Sut {
+            id: 1,
+            a: 2,
+            created_at: 3,
+            b: 4,
+        };
+
+        assert_eq!(actual.values(), [2, 4]);
+    }
+
+    #[test]
+    fn test_except___followed_by_other_clauses___parses_correctly() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, implicit_select_all: i32, except: a, with_names)]
+        struct Sut {
+            pub a: i32,
+            pub b: i32,
+        }
+
+        let actual = Sut { a: 1, b: 2 };
+
+        assert_eq!(actual.values(), [("b", &2)]);
+    }
+
+    #[test]
+    fn test_except___with_explicit_in_array___still_includes_field() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, implicit_select_all: _, except: a)]
+        struct Sut {
+            #[in_array(values)]
+            pub a: i32,
+            pub b: i32,
+        }
+
+        let actual = Sut { a: 1, b: 2 };
+
+        assert_eq!(actual.values(), [&1, &2]);
+    }
+
+    #[test]
+    fn test_except___on_tuple_structs_and_enums___excludes_listed_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, implicit_select_all: i32, except: 1)]
+        struct Tuple(i32, i32, i32);
+
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, implicit_select_all: i32, except: a)]
+        enum Enum {
+            One { a: i32, b: i32 },
+            Two { a: i32 },
+        }
+
+        assert_eq!(Tuple(1, 2, 3).values(), [&1, &3]);
+        assert_eq!(Enum::One { a: 1, b: 2 }.values(), vec![&2]);
+        assert!(Enum::Two { a: 1 }.values().is_empty());
+    }
+
+    #[test]
+    fn test_skip___excludes_field_only_from_given_method() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all: &i32, implicit_select_all: _)]
+        #[gen_array(fn others: &i32, implicit_select_all: _)]
+        struct Sut {
+            pub a: i32,
+            #[in_array(all { skip })]
+            pub b: i32,
+            pub c: i32,
+        }
+
+        let actual = Sut { a: 1, b: 2, c: 3 };
+
+        assert_eq!(actual.all(), [&1, &3]);
+        assert_eq!(actual.others(), [&1, &2, &3]);
+    }
+}
@@ -0,0 +1,187 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[derive(Arraygen)]
+    #[gen_array(fn all: f32, implicit_select_all: f32)]
+    #[gen_array(fn all_refs: &f32, implicit_select_all: f32)]
+    #[gen_array(fn all_muts: &mut f32, implicit_select_all: f32)]
+    struct Inner {
+        a: f32,
+        b: f32,
+    }
+
+    #[test]
+    fn test_flatten___with_inner_struct___splices_its_elements_in_place() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32, implicit_select_all: f32)]
+        struct Sut {
+            x: f32,
+            #[in_array(all { flatten })]
+            inner: Inner,
+            y: f32,
+        }
+
+        let actual = Sut {
+            x: 1.0,
+            inner: Inner { a: 2.0, b: 3.0 },
+            y: 4.0,
+        };
+
+        assert_eq!(actual.all(), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(Sut::ALL_LEN, 4);
+    }
+
+    #[test]
+    fn test_flatten___with_references___allows_modifying_inner_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all_refs: &f32, implicit_select_all: f32)]
+        #[gen_array(fn all_muts: &mut f32, implicit_select_all: f32)]
+        struct Sut {
+            #[in_array(all_refs { flatten }, all_muts { flatten })]
+            first: Inner,
+            x: f32,
+            #[in_array(all_refs { flatten }, all_muts { flatten })]
+            second: Inner,
+        }
+
+        let mut actual = Sut {
+            first: Inner { a: 1.0, b: 2.0 },
+            x: 3.0,
+            second: Inner { a: 4.0, b: 5.0 },
+        };
+
+        actual.all_muts().into_iter().for_each(|value| *value *= 10.0);
+
+        assert_eq!(actual.all_refs(), [&10.0, &20.0, &30.0, &40.0, &50.0]);
+        assert_eq!(Sut::ALL_MUTS_LEN, 5);
+    }
+
+    #[test]
+    fn test_flatten___with_nested_levels___splices_all_of_them() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32)]
+        struct Middle<'a> {
+            #[in_array(all { flatten })]
+            inner: &'a Inner,
+            #[in_array(all)]
+            c: f32,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32)]
+        struct Sut<'a> {
+            #[in_array(all { flatten })]
+            middle: Middle<'a>,
+            #[in_array(all { flatten })]
+            inner: Inner,
+        }
+
+        let inner = Inner { a: 1.0, b: 2.0 };
+        let actual = Sut {
+            middle: Middle {
+                inner: &inner,
+                c: 3.0,
+            },
+            inner: Inner { a: 4.0, b: 5.0 },
+        };
+
+        assert_eq!(actual.all(), [1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_flatten___on_generic_struct___splices_non_generic_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32, implicit_select_all: f32)]
+        struct Sut<T> {
+            #[in_array(all { flatten })]
+            inner: Inner,
+            other: T,
+        }
+
+        let actual = Sut {
+            inner: Inner { a: 1.0, b: 2.0 },
+            other: 3.0,
+        };
+
+        assert_eq!(actual.all(), [1.0, 2.0]);
+        assert_eq!(Sut::<f32>::ALL_LEN, 2);
+    }
+
+    #[test]
+    fn test_flatten___with_owned_values___moves_them_into_the_array() {
+        #[derive(Arraygen)]
+        #[gen_array(fn names: String, by_value, implicit_select_all: String)]
+        struct Person {
+            first: String,
+            last: String,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn names: String, by_value)]
+        struct Sut {
+            #[in_array(names { flatten })]
+            person: Person,
+            #[in_array(names)]
+            nickname: String,
+        }
+
+        let actual = Sut {
+            person: Person {
+                first: "Ada".to_string(),
+                last: "Lovelace".to_string(),
+            },
+            nickname: "Countess".to_string(),
+        };
+
+        assert_eq!(actual.names(), ["Ada", "Lovelace", "Countess"]);
+    }
+
+    #[test]
+    fn test_flatten___on_enums___splices_elements_of_active_variant() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32, implicit_select_all: f32)]
+        enum Sut {
+            Single(f32),
+            Nested(f32, #[in_array(all { flatten })] Inner),
+        }
+
+        assert_eq!(Sut::Single(1.0).all(), vec![1.0]);
+        assert_eq!(
+            Sut::Nested(1.0, Inner { a: 2.0, b: 3.0 }).all(),
+            vec![1.0, 2.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn test_flatten___on_generic_enums___splices_generic_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32, implicit_select_all: f32)]
+        struct Pair<T> {
+            a: f32,
+            tag: T,
+            b: f32,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn all: f32, implicit_select_all: f32)]
+        enum Sut<T> {
+            Single(f32),
+            Nested(#[in_array(all { flatten })] Pair<T>),
+        }
+
+        assert_eq!(Sut::<u8>::Single(1.0).all(), vec![1.0]);
+        assert_eq!(
+            Sut::Nested(Pair {
+                a: 2.0,
+                tag: 'x',
+                b: 3.0
+            })
+            .all(),
+            vec![2.0, 3.0]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+    use std::fmt::{Debug, Display};
+
+    #[test]
+    fn test_implicit_select_all___with_type_param___selects_fields_of_that_param() {
+        #[derive(Arraygen)]
+        #[gen_array(fn params: &T, implicit_select_all: T)]
+        struct Sut<T> {
+            a: T,
+            b: T,
+            c: u8,
+        }
+
+        let actual = Sut { a: 1, b: 2, c: 3 };
+
+        assert_eq!(actual.params(), [&1, &2]);
+    }
+
+    #[test]
+    fn test_implicit_select_all___with_bounded_type_param___returns_trait_objects() {
+        #[derive(Arraygen)]
+        #[gen_array(fn debuggables: &dyn Debug, implicit_select_all: T, U)]
+        struct Sut<T: Debug, U>
+        where
+            U: Debug,
+        {
+            a: T,
+            b: U,
+            c: Option<T>,
+        }
+
+        let actual = Sut {
+            a: 1,
+            b: "foo",
+            c: Some(2),
+        };
+
+        assert_eq!(format!("{:?}", actual.debuggables()), r#"[1, "foo"]"#);
+    }
+
+    #[test]
+    fn test_generic_params___with_many_params___selects_fields_of_any_of_them() {
+        #[derive(Arraygen)]
+        #[gen_array(fn displayables: &dyn Display, generic_params, names = displayable_names)]
+        struct Sut<'a, K: Display, V: Display, const N: usize> {
+            key: K,
+            value: V,
+            hits: u32,
+            others: [&'a V; N],
+            maybe: Option<K>,
+        }
+
+        let actual = Sut {
+            key: 'a',
+            value: 1.5,
+            hits: 3,
+            others: [],
+            maybe: None,
+        };
+
+        assert_eq!(actual.displayables().map(|d| d.to_string()), ["a", "1.5"]);
+        assert_eq!(Sut::<char, f64, 0>::displayable_names(), ["key", "value"]);
+    }
+
+    #[test]
+    fn test_generic_params___with_decorator_and_except___converts_remaining_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: f64, generic_params { into }, except: c)]
+        struct Sut<T: Into<f64> + Copy, U: Into<f64> + Copy> {
+            a: T,
+            b: U,
+            c: T,
+        }
+
+        let actual = Sut {
+            a: 1.0f32,
+            b: 2u8,
+            c: 3.0f32,
+        };
+
+        assert_eq!(actual.values(), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_generic_params___on_tuple_structs_and_enums___selects_params() {
+        #[derive(Arraygen)]
+        #[gen_array(fn params: &T, generic_params)]
+        struct Tuple<T>(T, u8, T);
+
+        #[derive(Arraygen)]
+        #[gen_array(fn params: &T, generic_params)]
+        enum Either<T> {
+            Left(T, u8),
+            Right { value: T },
+        }
+
+        assert_eq!(Tuple(1, 2, 3).params(), [&1, &3]);
+        assert_eq!(Either::Left(1, 2).params(), [&1]);
+        assert_eq!(Either::<i32>::Right { value: 3 }.params(), [&3]);
+    }
+}
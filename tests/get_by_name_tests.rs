@@ -0,0 +1,76 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_get_by_name___with_references___returns_field_with_that_name() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: &f32, implicit_select_all: f32, get_by_name = prices_by_name)]
+        #[gen_array(fn prices_mut: &mut f32, implicit_select_all: f32, get_by_name = prices_by_name_mut)]
+        struct Sut {
+            water: f32,
+            count: i32,
+            r#oil: f32,
+        }
+
+        let mut actual = Sut {
+            water: 1.0,
+            count: 2,
+            oil: 3.0,
+        };
+
+        if let Some(oil) = actual.prices_by_name_mut("oil") {
+            *oil = 30.0;
+        }
+
+        assert_eq!(actual.prices_by_name("water"), Some(&1.0));
+        assert_eq!(actual.prices_by_name("oil"), Some(&30.0));
+        assert_eq!(actual.prices_by_name("count"), None);
+        assert_eq!(actual.prices_by_name_mut("salt"), None);
+    }
+
+    #[test]
+    fn test_get_by_name___with_decorators_and_tuple_structs___returns_converted_field() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: i64, get_by_name = value_of)]
+        struct Sut(#[in_array(values { cast })] u8, #[in_array(values { into })] i32);
+
+        #[derive(Arraygen)]
+        #[gen_array(fn bytes: u8, implicit_select_all { try_into }: i32, get_by_name = byte_of)]
+        struct Fallible {
+            small: i32,
+            big: i32,
+        }
+
+        let fallible = Fallible { small: 1, big: 300 };
+
+        assert_eq!(Sut(1, 2).value_of("0"), Some(1));
+        assert_eq!(Sut(1, 2).value_of("1"), Some(2));
+        assert_eq!(fallible.byte_of("small").unwrap(), Some(1));
+        assert!(fallible.byte_of("big").is_err());
+        assert_eq!(fallible.byte_of("other").unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_by_name___on_enums___returns_field_of_active_variant() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, implicit_select_all: i32, get_by_name = value_of)]
+        enum Sut {
+            Point { x: i32, y: i32 },
+            Value(i32),
+            Empty,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, get_by_name = value_of, allow_empty)]
+        enum Never {}
+
+        assert_eq!(Sut::Point { x: 1, y: 2 }.value_of("y"), Some(&2));
+        assert_eq!(Sut::Value(3).value_of("0"), Some(&3));
+        assert_eq!(Sut::Value(3).value_of("x"), None);
+        assert_eq!(Sut::Empty.value_of("x"), None);
+    }
+}
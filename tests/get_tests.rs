@@ -0,0 +1,215 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_get___with_references___returns_element_at_index() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: &f32, implicit_select_all: f32, get = prices_get)]
+        #[gen_array(fn prices_mut: &mut f32, implicit_select_all: f32, get = prices_get_mut)]
+        struct Sut {
+            a: f32,
+            b: i32,
+            c: f32,
+        }
+
+        let mut actual = Sut {
+            a: 1.0,
+            b: 2,
+            c: 3.0,
+        };
+
+        if let Some(price) = actual.prices_get_mut(1) {
+            *price = 30.0;
+        }
+
+        assert_eq!(actual.prices_get(0), Some(&1.0));
+        assert_eq!(actual.prices_get(1), Some(&30.0));
+        assert_eq!(actual.prices_get(2), None);
+        assert_eq!(actual.prices_get_mut(2), None);
+    }
+
+    #[test]
+    fn test_get___with_decorators_and_names___returns_same_elements_as_method() {
+        fn double(value: &i32) -> i64 {
+            i64::from(*value) * 2
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn values: i64, with_names, get = value_at)]
+        struct Sut {
+            #[in_array(values { cast })]
+            a: u8,
+            #[in_array(values { with = double })]
+            b: i32,
+        }
+
+        let actual = Sut { a: 1, b: 2 };
+
+        assert_eq!(actual.value_at(0), Some(actual.values()[0]));
+        assert_eq!(actual.value_at(1), Some(("b", 4)));
+        assert_eq!(actual.value_at(2), None);
+    }
+
+    #[test]
+    fn test_get___with_try_into___returns_result_of_option() {
+        #[derive(Arraygen)]
+        #[gen_array(fn bytes: u8, implicit_select_all { try_into }: i32, get = byte_at)]
+        struct Sut {
+            a: i32,
+            b: i32,
+        }
+
+        let actual = Sut { a: 1, b: 300 };
+
+        assert_eq!(actual.byte_at(0).unwrap(), Some(1));
+        assert!(actual.byte_at(1).is_err());
+        assert_eq!(actual.byte_at(2).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get___on_enums_and_by_value___returns_element_at_index() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, implicit_select_all: i32, get = value_at)]
+        enum Sut {
+            A(i32, i32),
+            B { c: i32 },
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn into_strings: String, implicit_select_all: String, by_value, get = into_string_at)]
+        struct Owned {
+            a: String,
+            b: String,
+        }
+
+        assert_eq!(Sut::A(1, 2).value_at(1), Some(&2));
+        assert_eq!(Sut::B { c: 3 }.value_at(1), None);
+        assert_eq!(
+            Owned {
+                a: "a".into(),
+                b: "b".into()
+            }
+            .into_string_at(1),
+            Some("b".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get___with_flattened_fields___returns_element_at_index() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: f32, implicit_select_all: f32)]
+        struct Inner {
+            a: f32,
+            b: f32,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: f32, implicit_select_all: f32, get = price_at)]
+        struct Sut {
+            x: f32,
+            #[in_array(prices { flatten })]
+            inner: Inner,
+        }
+
+        let actual = Sut {
+            x: 1.0,
+            inner: Inner { a: 2.0, b: 3.0 },
+        };
+
+        assert_eq!(actual.price_at(2), Some(3.0));
+        assert_eq!(actual.price_at(3), None);
+    }
+
+    #[test]
+    fn test_get___with_try_into___converts_only_requested_element() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: f32, implicit_select_all: f32)]
+        struct Inner {
+            a: f32,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn bytes: u8, implicit_select_all { try_into }: i32, get = byte_at)]
+        struct Plain {
+            a: i32,
+            b: i32,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn bytes: u8, implicit_select_all { try_into }: i32, get = byte_at)]
+        enum Enum {
+            A(i32, i32),
+        }
+
+        #[derive(Clone)]
+        struct Unconvertible;
+
+        impl std::convert::TryFrom<Unconvertible> for f32 {
+            type Error = &'static str;
+
+            fn try_from(_: Unconvertible) -> Result<f32, Self::Error> {
+                Err("unconvertible")
+            }
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: f32, get = price_at)]
+        struct Flattened {
+            #[in_array(prices { try_into })]
+            a: Unconvertible,
+            #[in_array(prices { flatten })]
+            inner: Inner,
+            #[in_array(prices { try_into })]
+            b: i16,
+        }
+
+        assert_eq!(Plain { a: 1, b: 300 }.byte_at(0).unwrap(), Some(1));
+        assert!(Plain { a: 300, b: 1 }.byte_at(0).is_err());
+        assert_eq!(Enum::A(1, 300).byte_at(0).unwrap(), Some(1));
+        assert!(Enum::A(1, 300).byte_at(1).is_err());
+        assert_eq!(Enum::A(1, 300).byte_at(2).unwrap(), None);
+
+        let flattened = Flattened {
+            a: Unconvertible,
+            inner: Inner { a: 2.0 },
+            b: 3,
+        };
+        assert!(flattened.price_at(0).is_err());
+        assert_eq!(flattened.price_at(1).unwrap(), Some(2.0));
+        assert_eq!(flattened.price_at(2).unwrap(), Some(3.0));
+        assert_eq!(flattened.price_at(3).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get___on_enums_with_flattened_fields___returns_element_at_index() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &mut i32, implicit_select_all: i32)]
+        struct Inner {
+            a: i32,
+            b: i32,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &mut i32, implicit_select_all: i32, get = value_at)]
+        enum Sut {
+            Plain(i32),
+            Nested(i32, #[in_array(values { flatten })] Inner, i32),
+        }
+
+        let mut actual = Sut::Nested(1, Inner { a: 2, b: 3 }, 4);
+        if let Some(value) = actual.value_at(2) {
+            *value = 30;
+        }
+
+        assert_eq!(actual.value_at(0), Some(&mut 1));
+        assert_eq!(actual.value_at(2), Some(&mut 30));
+        assert_eq!(actual.value_at(3), Some(&mut 4));
+        assert_eq!(actual.value_at(4), None);
+        assert_eq!(Sut::Plain(5).value_at(0), Some(&mut 5));
+        assert_eq!(Sut::Plain(5).value_at(1), None);
+    }
+}
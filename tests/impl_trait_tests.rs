@@ -0,0 +1,89 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    mod plugins {
+        pub trait Sensor {
+            fn read(&self) -> i32;
+        }
+
+        pub trait HasSensors {
+            fn sensors(&self) -> [&dyn Sensor; 2];
+        }
+
+        pub trait HasSensorValues {
+            fn sensor_values(&mut self) -> Vec<&mut i32>;
+        }
+    }
+
+    use plugins::Sensor;
+
+    struct Thermometer(i32);
+
+    impl Sensor for Thermometer {
+        fn read(&self) -> i32 {
+            self.0
+        }
+    }
+
+    fn sum_readings<T: plugins::HasSensors>(item: &T) -> i32 {
+        item.sensors().iter().map(|sensor| sensor.read()).sum()
+    }
+
+    #[test]
+    fn test_impl_trait___with_matching_trait___implements_it_instead_of_inherent_method() {
+        #[derive(Arraygen)]
+        #[gen_array(fn sensors: &dyn Sensor, implicit_select_all: Thermometer, impl_trait = plugins::HasSensors)]
+        struct Kitchen {
+            fridge: Thermometer,
+            oven: Thermometer,
+            name: String,
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn sensors: &dyn Sensor, impl_trait = plugins::HasSensors)]
+        struct Garden<T: Sensor> {
+            #[in_array(sensors)]
+            soil: T,
+            #[in_array(sensors)]
+            air: Thermometer,
+        }
+
+        let kitchen = Kitchen {
+            fridge: Thermometer(4),
+            oven: Thermometer(180),
+            name: "kitchen".into(),
+        };
+        let garden = Garden {
+            soil: Thermometer(15),
+            air: Thermometer(20),
+        };
+
+        assert_eq!(sum_readings(&kitchen), 184);
+        assert_eq!(sum_readings(&garden), 35);
+        assert_eq!(Kitchen::SENSORS_LEN, 2);
+    }
+
+    #[test]
+    fn test_impl_trait___on_enums___implements_trait() {
+        use plugins::HasSensorValues;
+
+        #[derive(Arraygen)]
+        #[gen_array(fn sensor_values: &mut i32, implicit_select_all: i32, impl_trait = HasSensorValues)]
+        enum Device {
+            Single(i32),
+            Double { first: i32, second: i32 },
+        }
+
+        let mut actual = Device::Double {
+            first: 1,
+            second: 2,
+        };
+        actual.sensor_values().into_iter().for_each(|value| *value *= 10);
+
+        assert_eq!(actual.sensor_values(), [&mut 10, &mut 20]);
+    }
+}
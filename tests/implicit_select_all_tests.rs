@@ -130,4 +130,27 @@ mod tests {
         assert_eq!(actual.options().len(), 1);
         assert_eq!(actual.tracked().len(), 2);
     }
+
+    #[test]
+    fn test_implicit_select_all___with_fully_qualified_prelude_types___selects_them_too() {
+        #[derive(Arraygen)]
+        #[gen_array(fn texts: &String, implicit_select_all: String)]
+        #[gen_array(fn buffers: &Vec<u8>, implicit_select_all: ::std::vec::Vec<u8>)]
+        struct Sut {
+            pub a: String,
+            pub b: std::string::String,
+            pub c: Vec<u8>,
+            pub d: std::vec::Vec<u8>,
+        }
+
+        let actual = Sut {
+            a: "a".to_string(),
+            b: "b".to_string(),
+            c: vec![1],
+            d: vec![2],
+        };
+
+        assert_eq!(actual.texts(), [&"a", &"b"]);
+        assert_eq!(actual.buffers(), [&vec![1], &vec![2]]);
+    }
 }
@@ -0,0 +1,63 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[derive(Arraygen)]
+    #[gen_array(pub fn get_all_prices: f32, implicit_select_all: f32)]
+    #[gen_array(fn get_ids: &u32, allow_empty)]
+    pub struct Prices {
+        pub water: f32,
+        pub oil: f32,
+        pub tomato: f32,
+        pub id: u32,
+    }
+
+    const _: () = assert!(Prices::GET_ALL_PRICES_LEN == 3);
+
+    #[test]
+    fn test_len_const___matches_length_of_generated_array() {
+        let actual = Prices {
+            water: 1.0,
+            oil: 2.0,
+            tomato: 3.0,
+            id: 4,
+        };
+
+        assert_eq!(Prices::GET_ALL_PRICES_LEN, actual.get_all_prices().len());
+        assert_eq!(Prices::GET_IDS_LEN, 0);
+    }
+
+    #[test]
+    fn test_len_const___can_be_used_to_size_arrays_in_other_structs() {
+        struct History {
+            last_prices: [f32; Prices::GET_ALL_PRICES_LEN],
+        }
+
+        let prices = Prices {
+            water: 1.0,
+            oil: 2.0,
+            tomato: 3.0,
+            id: 4,
+        };
+        let history = History {
+            last_prices: prices.get_all_prices(),
+        };
+
+        assert_eq!(history.last_prices, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_len_const___with_generics_and_raw_identifiers___compiles_correctly() {
+        #[derive(Arraygen)]
+        #[gen_array(fn r#ref: &T, implicit_select_all: T)]
+        struct Pair<T> {
+            a: T,
+            b: T,
+        }
+
+        assert_eq!(Pair::<u8>::REF_LEN, 2);
+    }
+}
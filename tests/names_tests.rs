@@ -0,0 +1,53 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_names___with_in_array___returns_names_in_same_order_as_values() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: f32, names = price_names)]
+        struct Prices {
+            #[in_array(prices)]
+            water: f32,
+            id: u32,
+            #[in_array(prices)]
+            oil: f32,
+        }
+
+        let actual = Prices {
+            water: 1.0,
+            id: 0,
+            oil: 2.0,
+        };
+
+        assert_eq!(Prices::price_names(), ["water", "oil"]);
+        assert_eq!(actual.prices(), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_names___with_implicit_select_all___is_usable_in_const_context() {
+        #[derive(Arraygen)]
+        #[gen_array(pub fn prices: &f32, implicit_select_all: f32, names = price_names)]
+        struct Prices {
+            pub water: f32,
+            pub r#type: u8,
+            pub r#match: f32,
+        }
+
+        const NAMES: [&str; 2] = Prices::price_names();
+
+        assert_eq!(NAMES, ["water", "match"]);
+    }
+
+    #[test]
+    fn test_names___on_tuple_struct___returns_indexes() {
+        #[derive(Arraygen)]
+        #[gen_array(fn channels: u8, implicit_select_all: u8, names = channel_names,)]
+        struct Rgb(u8, u8, u8);
+
+        assert_eq!(Rgb::channel_names(), ["0", "1", "2"]);
+    }
+}
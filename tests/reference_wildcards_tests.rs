@@ -0,0 +1,72 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+    use std::fmt::{Debug, Display};
+
+    #[test]
+    fn test_reference_wildcard___selects_shared_references_only() {
+        #[derive(Arraygen)]
+        #[gen_array(fn borrowed: &dyn Debug, implicit_select_all: &_)]
+        #[gen_array(fn borrowed_mut: &dyn Debug, implicit_select_all: &mut _)]
+        struct Sut<'a, 'b> {
+            pub a: &'a str,
+            pub b: &'b [u8],
+            pub c: &'a mut i32,
+            pub d: i32,
+        }
+
+        let mut value = 3;
+        let actual = Sut {
+            a: "a",
+            b: &[1, 2],
+            c: &mut value,
+            d: 4,
+        };
+
+        assert_eq!(format!("{:?}", actual.borrowed()), r#"["a", [1, 2]]"#);
+        assert_eq!(format!("{:?}", actual.borrowed_mut()), "[3]");
+    }
+
+    #[test]
+    fn test_lifetime_wildcard___selects_references_with_any_lifetime() {
+        #[derive(Arraygen)]
+        #[gen_array(fn texts: &str, implicit_select_all: &'_ str)]
+        struct Sut<'a, 'b> {
+            pub a: &'a str,
+            pub b: &'b str,
+            pub c: &'static str,
+            pub d: String,
+        }
+
+        let actual = Sut {
+            a: "a",
+            b: "b",
+            c: "c",
+            d: "d".to_string(),
+        };
+
+        assert_eq!(actual.texts(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_trait_wildcard___selects_any_trait_object() {
+        #[derive(Arraygen)]
+        #[gen_array(fn boxes: &dyn std::any::Any, implicit_select_all: Box<dyn _>, with_names)]
+        struct Sut {
+            pub a: Box<dyn Display>,
+            pub b: Box<dyn Debug + Send>,
+            pub c: Box<i32>,
+        }
+
+        let actual = Sut {
+            a: Box::new(1),
+            b: Box::new(2),
+            c: Box::new(3),
+        };
+
+        assert_eq!(actual.boxes().map(|(name, _)| name), ["a", "b"]);
+    }
+}
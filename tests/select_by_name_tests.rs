@@ -0,0 +1,104 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_select_by_name___with_suffix_pattern___selects_matching_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn prices: &f32, select_by_name: "*_price")]
+        struct Sut {
+            pub water_price: f32,
+            pub oil_price: f32,
+            pub tax: f32,
+        }
+
+        let actual = Sut {
+            water_price: 1.0,
+            oil_price: 2.0,
+            tax: 3.0,
+        };
+
+        assert_eq!(actual.prices(), [&1.0, &2.0]);
+    }
+
+    #[test]
+    fn test_select_by_name___with_many_patterns_and_decorators___selects_matching_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn timeouts: u64, select_by_name { cast }: "*_timeout_ms", "enable_?", with_names)]
+        struct Sut {
+            pub read_timeout_ms: u32,
+            pub write_timeout_ms: u16,
+            pub enable_x: bool,
+            pub enable_xy: bool,
+        }
+
+        let actual = Sut {
+            read_timeout_ms: 100,
+            write_timeout_ms: 200,
+            enable_x: true,
+            enable_xy: true,
+        };
+
+        assert_eq!(
+            actual.timeouts(),
+            [
+                ("read_timeout_ms", 100),
+                ("write_timeout_ms", 200),
+                ("enable_x", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_by_name___combined_with_implicit_select_all___selects_each_field_once() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: f32, implicit_select_all: f32, select_by_name { cast }: "*_price", except: tax_price)]
+        struct Sut {
+            pub water_price: f32,
+            pub oil_price: u8,
+            pub tax_price: f32,
+            pub other: f32,
+            pub count: u8,
+        }
+
+        let actual = Sut {
+            water_price: 1.0,
+            oil_price: 2,
+            tax_price: 3.0,
+            other: 4.0,
+            count: 5,
+        };
+
+        assert_eq!(actual.values(), [1.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_select_by_name___with_in_array_override___uses_field_decorators() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: i32, select_by_name { cast }: "value_*")]
+        struct Sut {
+            pub value_a: f32,
+            #[in_array(values { override_implicit, unsafe_transmute })]
+            pub value_b: f32,
+        }
+
+        let actual = Sut {
+            value_a: 1.5,
+            value_b: 1.0,
+        };
+
+        assert_eq!(actual.values(), [1, 1065353216]);
+    }
+
+    #[test]
+    fn test_select_by_name___on_tuple_structs___matches_indexes() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &i32, select_by_name: "1", "2")]
+        struct Sut(i32, i32, i32);
+
+        assert_eq!(Sut(1, 2, 3).values(), [&2, &3]);
+    }
+}
@@ -0,0 +1,77 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_select_vis___with_pub___selects_only_public_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn public_fields: f64, select_vis { cast }: pub, names = public_names)]
+        struct Sut {
+            pub a: f32,
+            b: f32,
+            pub(crate) c: f32,
+            pub d: i32,
+        }
+
+        let actual = Sut {
+            a: 1.0,
+            b: 2.0,
+            c: 3.0,
+            d: 4,
+        };
+
+        assert_eq!(actual.public_fields(), [1.0, 4.0]);
+        assert_eq!(Sut::public_names(), ["a", "d"]);
+    }
+
+    #[test]
+    fn test_select_vis___with_many_visibilities___selects_any_of_them() {
+        #[derive(Arraygen)]
+        #[gen_array(fn visible: &i32, select_vis: pub(crate), pub(super), except: d)]
+        struct Sut {
+            pub a: i32,
+            pub(crate) b: i32,
+            pub(super) c: i32,
+            pub(crate) d: i32,
+            e: i32,
+        }
+
+        let actual = Sut {
+            a: 1,
+            b: 2,
+            c: 3,
+            d: 4,
+            e: 5,
+        };
+
+        assert_eq!(actual.visible(), [&2, &3]);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_select_vis___with_equivalent_spellings___selects_them() {
+        #[derive(Arraygen)]
+        #[gen_array(fn crate_visible: &i32, select_vis: pub(crate))]
+        struct Sut {
+            pub(crate) a: i32,
+            pub(in crate) b: i32,
+            pub(super) c: i32,
+        }
+
+        let actual = Sut { a: 1, b: 2, c: 3 };
+
+        assert_eq!(actual.crate_visible(), [&1, &2]);
+    }
+
+    #[test]
+    fn test_select_vis___on_tuple_structs___selects_public_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn visible: &i32, select_vis: pub)]
+        struct Sut(pub i32, i32, pub i32);
+
+        assert_eq!(Sut(1, 2, 3).visible(), [&1, &3]);
+    }
+}
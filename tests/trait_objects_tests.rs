@@ -1,5 +1,6 @@
 extern crate arraygen;
 
+#[allow(dead_code)]
 #[allow(non_snake_case)]
 mod tests {
     use arraygen::Arraygen;
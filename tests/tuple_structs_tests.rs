@@ -0,0 +1,58 @@
+extern crate arraygen;
+
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_tuple_struct___with_in_array_on_positional_fields___returns_selected_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn red_and_blue: &u8)]
+        struct Rgb(
+            #[in_array(red_and_blue)] u8,
+            u8,
+            #[in_array(red_and_blue)] u8,
+        );
+
+        let actual = Rgb(1, 2, 3);
+
+        assert_eq!(actual.red_and_blue(), [&1, &3]);
+        assert_eq!(actual.1, 2);
+    }
+
+    #[test]
+    fn test_tuple_struct___with_implicit_select_all___returns_matching_fields() {
+        #[derive(Arraygen)]
+        #[gen_array(fn channels: &mut u8, implicit_select_all: u8)]
+        struct Rgba(u8, u8, u8, pub f32);
+
+        let mut actual = Rgba(1, 2, 3, 0.5);
+        for channel in actual.channels() {
+            *channel *= 2;
+        }
+
+        assert_eq!((actual.0, actual.1, actual.2, actual.3), (2, 4, 6, 0.5));
+    }
+
+    #[test]
+    fn test_tuple_struct___with_generics_and_where_clause___compiles_correctly() {
+        #[derive(Arraygen)]
+        #[gen_array(fn values: &T, implicit_select_all: T)]
+        struct Pair<T>(T, T)
+        where
+            T: Copy;
+
+        let actual = Pair(1, 2);
+
+        assert_eq!(actual.values(), [&1, &2]);
+    }
+
+    #[test]
+    fn test_newtype_struct___with_cast___returns_casted_value() {
+        #[derive(Arraygen)]
+        #[gen_array(fn as_f64: f64, implicit_select_all { cast }: u32)]
+        struct Meters(u32);
+
+        assert_eq!(Meters(3).as_f64(), [3.0]);
+    }
+}
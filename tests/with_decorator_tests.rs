@@ -0,0 +1,121 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    mod labels {
+        pub fn from_number(value: &i32) -> String {
+            format!("#{}", value)
+        }
+
+        pub fn from_flag(value: &bool) -> String {
+            if *value { "yes" } else { "no" }.to_string()
+        }
+
+        pub fn uppercase(text: &&str) -> String {
+            text.to_uppercase()
+        }
+    }
+
+    #[test]
+    fn test_with_on_in_array___calls_function_with_field_reference() {
+        #[derive(Arraygen)]
+        #[gen_array(fn labels: String)]
+        struct Sut {
+            #[in_array(labels { with = labels::from_number })]
+            pub a: i32,
+            #[in_array(labels { with = labels::from_flag })]
+            pub b: bool,
+            #[in_array(labels { with = labels::uppercase })]
+            pub c: &'static str,
+        }
+
+        let actual = Sut {
+            a: 1,
+            b: true,
+            c: "foo",
+        };
+
+        assert_eq!(actual.labels(), ["#1", "yes", "FOO"]);
+    }
+
+    #[test]
+    fn test_with_on_implicit_select_all___calls_function_for_every_selected_field() {
+        #[derive(Arraygen)]
+        #[gen_array(fn labels: String, implicit_select_all { with = labels::from_number }: i32)]
+        struct Sut {
+            pub a: i32,
+            pub b: i32,
+            #[in_array(labels { override_implicit, with = ToString::to_string })]
+            pub c: i32,
+        }
+
+        let actual = Sut { a: 1, b: 2, c: 3 };
+
+        assert_eq!(actual.labels(), ["#1", "#2", "3"]);
+    }
+
+    #[test]
+    fn test_with_returning_references___returns_projected_references() {
+        struct Point {
+            x: f32,
+            y: f32,
+        }
+
+        fn x_of(point: &mut Point) -> &mut f32 {
+            &mut point.x
+        }
+
+        #[derive(Arraygen)]
+        #[gen_array(fn xs: &mut f32, implicit_select_all { with = x_of }: Point)]
+        struct Sut {
+            pub a: Point,
+            pub b: Point,
+        }
+
+        let mut actual = Sut {
+            a: Point { x: 1.0, y: 1.0 },
+            b: Point { x: 2.0, y: 2.0 },
+        };
+
+        for x in actual.xs() {
+            *x += 10.0;
+        }
+
+        assert_eq!(actual.a.x, 11.0);
+        assert_eq!(actual.b.x, 12.0);
+        assert_eq!(actual.b.y, 2.0);
+    }
+
+    #[test]
+    fn test_with_and_by_value___calls_function_with_owned_field() {
+        #[derive(Arraygen)]
+        #[gen_array(fn into_bytes: Vec<u8>, implicit_select_all { with = String::into_bytes }: String, by_value)]
+        struct Sut {
+            pub a: String,
+            pub b: String,
+        }
+
+        let actual = Sut {
+            a: "foo".to_string(),
+            b: "barbaz".to_string(),
+        };
+
+        assert_eq!(actual.into_bytes(), [b"foo".to_vec(), b"barbaz".to_vec()]);
+    }
+
+    #[test]
+    fn test_with_on_enum___calls_function_for_fields_of_active_variant() {
+        #[derive(Arraygen)]
+        #[gen_array(fn labels: String, implicit_select_all { with = labels::from_number }: i32)]
+        enum Sut {
+            One(i32),
+            Two { a: i32, b: bool },
+        }
+
+        assert_eq!(Sut::One(1).labels(), vec!["#1"]);
+        assert_eq!(Sut::Two { a: 2, b: false }.labels(), vec!["#2"]);
+    }
+}
@@ -0,0 +1,72 @@
+extern crate arraygen;
+
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+mod tests {
+    use arraygen::Arraygen;
+
+    #[test]
+    fn test_with_names___with_in_array___returns_name_and_value_pairs() {
+        #[derive(Arraygen)]
+        #[gen_array(fn priced: &f32, with_names)]
+        struct Prices {
+            #[in_array(priced)]
+            water: f32,
+            id: u32,
+            #[in_array(priced)]
+            oil: f32,
+        }
+
+        let actual = Prices {
+            water: 1.0,
+            id: 0,
+            oil: 2.0,
+        };
+
+        assert_eq!(actual.priced(), [("water", &1.0), ("oil", &2.0)]);
+    }
+
+    #[test]
+    fn test_with_names___with_trait_objects_and_implicit_select_all___coerces_values() {
+        use std::fmt::Debug;
+
+        #[derive(Arraygen)]
+        #[gen_array(fn debug_entries: &dyn Debug, with_names, implicit_select_all: _)]
+        struct Config {
+            name: String,
+            retries: u8,
+        }
+
+        let actual = Config {
+            name: "foo".into(),
+            retries: 3,
+        };
+
+        let entries: Vec<String> = actual
+            .debug_entries()
+            .iter()
+            .map(|(name, value)| format!("{}={:?}", name, value))
+            .collect();
+
+        assert_eq!(entries, ["name=\"foo\"", "retries=3"]);
+    }
+
+    #[test]
+    fn test_with_names___with_mut_refs_on_enum___returns_pairs_of_active_variant() {
+        #[derive(Arraygen)]
+        #[gen_array(fn numbers: &mut i32, implicit_select_all: i32, with_names)]
+        enum Shape {
+            Point(i32, i32),
+            Circle { radius: i32 },
+        }
+
+        let mut actual = Shape::Circle { radius: 2 };
+        for (name, value) in actual.numbers() {
+            assert_eq!(name, "radius");
+            *value = 5;
+        }
+
+        assert_eq!(actual.numbers(), [("radius", &mut 5)]);
+        assert_eq!(Shape::Point(1, 2).numbers(), [("0", &mut 1), ("1", &mut 2)]);
+    }
+}